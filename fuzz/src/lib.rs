@@ -0,0 +1,21 @@
+// Shared by the fuzz targets: run arbitrary input through a day the same way the aoc runner does.
+// Errors are fine, since most inputs are nonsense; only panics (and hangs) are bugs.
+use aoclib::days;
+use aoclib::diagnostics;
+use aoclib::parse::Strictness;
+use aoclib::solution::Part;
+
+pub fn parse_and_solve(day: u8, data: &[u8]) {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    let day = days::get(day).expect("day is not registered");
+    // Collect diagnostics instead of printing them, and throw them away after every run
+    diagnostics::capture(true);
+    if let Ok(parsed) = (day.parse)(input, Strictness::Strict) {
+        for part in Part::ALL {
+            let _ = parsed.solve(part);
+        }
+    }
+    diagnostics::take();
+}
@@ -0,0 +1,179 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+use nom::{
+    branch::alt,
+    bytes::complete::{is_not, tag},
+    character::complete::{char, line_ending, space1, u8, usize},
+    combinator::{eof, map, map_res, not, opt, recognize, value},
+    multi::many0,
+    sequence::{preceded, terminated},
+    IResult, Parser,
+};
+
+use crate::solution::Part;
+
+// Which input of a day an answer belongs to
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum InputFile {
+    // input/DD
+    Real,
+    // examples/DD/N
+    Example(usize),
+}
+
+impl Display for InputFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputFile::Real => write!(f, "input"),
+            InputFile::Example(n) => write!(f, "example {n}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Key {
+    pub day: u8,
+    pub input: InputFile,
+    pub part: Part,
+}
+
+// The expected answers, one per line:
+//     DD P input ANSWER
+//     DD P example N ANSWER
+// Blank lines and lines starting with # are ignored, but kept with the answer after them (or at the
+// end) so that saving the answers again doesn't lose them.
+#[derive(Debug, Default, Clone)]
+pub struct Answers {
+    answers: BTreeMap<Key, String>,
+    notes: BTreeMap<Key, Vec<String>>,
+    trailing: Vec<String>,
+}
+
+// A line of the answers file
+#[derive(Debug, Clone)]
+enum Line {
+    Answer(Key, String),
+    Ignored(String),
+}
+
+fn part(input: &str) -> IResult<&str, Part> {
+    alt((value(Part::One, char('1')), value(Part::Two, char('2')))).parse(input)
+}
+
+pub(crate) fn input_file(input: &str) -> IResult<&str, InputFile> {
+    alt((
+        value(InputFile::Real, tag("input")),
+        map(
+            preceded((tag("example"), space1), usize),
+            InputFile::Example,
+        ),
+    ))
+    .parse(input)
+}
+
+fn entry(input: &str) -> IResult<&str, (Key, String)> {
+    map(
+        (
+            terminated(u8, space1),
+            terminated(part, space1),
+            terminated(input_file, space1),
+            map_res(is_not("\r\n"), |a: &str| match a.trim() {
+                "" => Err("empty answer"),
+                a => Ok(a.to_string()),
+            }),
+        ),
+        |(day, part, input, answer)| (Key { day, input, part }, answer),
+    )
+    .parse(input)
+}
+
+// A blank line or comment, which may be the last line without a newline
+pub(crate) fn ignored(input: &str) -> IResult<&str, &str> {
+    preceded(
+        not(eof),
+        terminated(
+            recognize(opt(preceded(char('#'), opt(is_not("\r\n"))))),
+            alt((line_ending, eof)),
+        ),
+    )
+    .parse(input)
+}
+
+fn parse_answers(input: &str) -> IResult<&str, Vec<Line>> {
+    many0(alt((
+        map(terminated(entry, opt(line_ending)), |(key, answer)| {
+            Line::Answer(key, answer)
+        }),
+        map(ignored, |l| Line::Ignored(l.to_string())),
+    )))
+    .parse(input)
+}
+
+impl Answers {
+    pub fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        let (rest, entries) = parse_answers(input).map_err(|e| format!("Invalid answers: {e}"))?;
+        if !rest.is_empty() {
+            let line = rest.lines().next().unwrap_or_default();
+            return Err(format!("Invalid answers: could not parse line {line:?}").into());
+        }
+        let mut answers = Answers::default();
+        for line in entries {
+            match line {
+                Line::Answer(key, answer) => {
+                    let notes = std::mem::take(&mut answers.trailing);
+                    if !notes.is_empty() {
+                        answers.notes.entry(key).or_default().extend(notes);
+                    }
+                    answers.answers.insert(key, answer);
+                }
+                Line::Ignored(line) => answers.trailing.push(line),
+            }
+        }
+        Ok(answers)
+    }
+
+    // Load the answers from path, or start from scratch if it doesn't exist yet
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(s) => Self::parse(&s),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("Could not read {}: {e}", path.display()).into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        fs::write(path, self.to_string())
+            .map_err(|e| format!("Could not write {}: {e}", path.display()).into())
+    }
+
+    pub fn get(&self, key: &Key) -> Option<&str> {
+        self.answers.get(key).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, key: Key, answer: String) {
+        self.answers.insert(key, answer);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Key, &str)> {
+        self.answers.iter().map(|(k, a)| (k, a.as_str()))
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (key @ Key { day, input, part }, answer) in &self.answers {
+            for note in self.notes.get(key).into_iter().flatten() {
+                writeln!(f, "{note}")?;
+            }
+            writeln!(f, "{day:02} {part} {input} {answer}")?;
+        }
+        for note in &self.trailing {
+            writeln!(f, "{note}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,171 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{line_ending, space1, u64, u8},
+    combinator::{map, opt, value},
+    multi::many0,
+    sequence::terminated,
+    IResult, Parser,
+};
+
+use crate::answers::{ignored, input_file, InputFile};
+use crate::solution::Part;
+
+// The phases of solving a day that are timed separately
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => f.pad("parse"),
+            Phase::Solve(Part::One) => f.pad("part1"),
+            Phase::Solve(Part::Two) => f.pad("part2"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Option<Stats> {
+        samples.sort();
+        Some(Stats {
+            min: *samples.first()?,
+            median: samples[samples.len() / 2],
+            max: *samples.last()?,
+        })
+    }
+}
+
+// Run f n times (at least once), returning the result of the last run and the timing statistics
+pub fn measure<T>(
+    n: usize,
+    mut f: impl FnMut() -> Result<T, Box<dyn Error>>,
+) -> Result<(T, Stats), Box<dyn Error>> {
+    let mut samples = Vec::with_capacity(n);
+    let mut out = None;
+    for _ in 0..n.max(1) {
+        let now = Instant::now();
+        out = Some(f()?);
+        samples.push(now.elapsed());
+    }
+    // Both unwraps are safe because f has run at least once
+    Ok((out.unwrap(), Stats::from_samples(&mut samples).unwrap()))
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Key {
+    pub day: u8,
+    pub input: InputFile,
+    pub phase: Phase,
+}
+
+// Benchmark results, one per line with the durations in nanoseconds:
+//     DD input PHASE MIN MEDIAN MAX
+//     DD example N PHASE MIN MEDIAN MAX
+// Blank lines and lines starting with # are skipped, like in the answers file.
+#[derive(Debug, Default, Clone)]
+pub struct Results(BTreeMap<Key, Stats>);
+
+fn phase(input: &str) -> IResult<&str, Phase> {
+    alt((
+        value(Phase::Parse, tag("parse")),
+        value(Phase::Solve(Part::One), tag("part1")),
+        value(Phase::Solve(Part::Two), tag("part2")),
+    ))
+    .parse(input)
+}
+
+fn nanos(input: &str) -> IResult<&str, Duration> {
+    map(u64, Duration::from_nanos).parse(input)
+}
+
+fn record(input: &str) -> IResult<&str, (Key, Stats)> {
+    map(
+        (
+            terminated(u8, space1),
+            terminated(input_file, space1),
+            terminated(phase, space1),
+            terminated(nanos, space1),
+            terminated(nanos, space1),
+            nanos,
+        ),
+        |(day, input, phase, min, median, max)| {
+            (Key { day, input, phase }, Stats { min, median, max })
+        },
+    )
+    .parse(input)
+}
+
+fn parse_results(input: &str) -> IResult<&str, Vec<(Key, Stats)>> {
+    map(
+        many0(alt((
+            map(terminated(record, opt(line_ending)), Some),
+            value(None, ignored),
+        ))),
+        |v| v.into_iter().flatten().collect(),
+    )
+    .parse(input)
+}
+
+impl Results {
+    pub fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        let (rest, entries) =
+            parse_results(input).map_err(|e| format!("Invalid benchmark results: {e}"))?;
+        if !rest.trim().is_empty() {
+            let line = rest.lines().next().unwrap_or_default();
+            return Err(format!("Invalid benchmark results: could not parse line {line:?}").into());
+        }
+        Ok(Results(entries.into_iter().collect()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self, Box<dyn Error>> {
+        let s = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+        Self::parse(&s)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        fs::write(path, self.to_string())
+            .map_err(|e| format!("Could not write {}: {e}", path.display()).into())
+    }
+
+    pub fn get(&self, key: &Key) -> Option<&Stats> {
+        self.0.get(key)
+    }
+
+    pub fn insert(&mut self, key: Key, stats: Stats) {
+        self.0.insert(key, stats);
+    }
+}
+
+impl Display for Results {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (Key { day, input, phase }, Stats { min, median, max }) in &self.0 {
+            writeln!(
+                f,
+                "{day:02} {input} {phase} {} {} {}",
+                min.as_nanos(),
+                median.as_nanos(),
+                max.as_nanos()
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -1,28 +1,8 @@
-#![feature(iter_map_windows)]
-#![feature(int_roundings)]
-
 use std::error::Error;
 use std::io::prelude::*;
 
-use nom::{
-    branch::alt,
-    character::complete::{char, i32, newline},
-    combinator::map,
-    multi::separated_list1,
-    sequence::preceded,
-    IResult, Parser,
-};
-
-fn parse_code(input: &str) -> IResult<&str, Vec<i32>> {
-    separated_list1(
-        newline,
-        alt((
-            preceded(char('R'), i32),
-            map(preceded(char('L'), i32), |s| -s),
-        )),
-    )
-    .parse(input)
-}
+use aoclib::days::day01::Day01;
+use aoclib::solution::Solution;
 
 fn main() -> Result<(), Box<dyn Error>> {
     let stdin = std::io::stdin();
@@ -30,31 +10,13 @@ fn main() -> Result<(), Box<dyn Error>> {
     let mut input = String::new();
 
     stdin.read_to_string(&mut input)?;
-    let (_, rots) = parse_code(&input).map_err(|e| format!("Invalid input: {e}"))?;
-
-    let initial: i32 = 50;
-    let wrap: i32 = 100;
-    let answer = rots
-        .iter()
-        .scan(initial, |s, r| {
-            *s = *s + r;
-            Some(*s)
-        })
-        .filter(|r| r % wrap == 0)
-        .count();
-
-    println!("The number of zeros is: {:?}", answer);
+    let parsed = Day01::parse(&input)?;
 
-    let answer: i32 = rots
-        .iter()
-        .scan(initial, |s, r| {
-            *s = *s + r;
-            Some(*s)
-        })
-        .map_windows(|[a, b]| (a.div_floor(wrap) - b.div_floor(wrap)).abs())
-        .sum();
+    let answer = Day01::part_one(&parsed)?;
+    println!("The number of zeros is: {answer}");
 
-    println!("The number of zero crossings is: {:?}", answer);
+    let answer = Day01::part_two(&parsed)?;
+    println!("The number of zero crossings is: {answer}");
 
-    return Ok(());
+    Ok(())
 }
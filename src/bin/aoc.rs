@@ -0,0 +1,401 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use clap::Parser;
+use serde_json::json;
+
+use aoclib::answers::{Answers, InputFile, Key};
+use aoclib::bench::{self, measure, Phase, Results, Stats};
+use aoclib::days::{self, Day, DAYS};
+use aoclib::diagnostics::{self, Diagnostic};
+use aoclib::generate;
+use aoclib::parse::Strictness;
+use aoclib::solution::Part;
+
+#[derive(Parser)]
+#[command(about = "Run the Advent of Code 2025 solutions")]
+struct Args {
+    /// The day to run
+    #[arg(short, long, required_unless_present_any = ["all", "verify"])]
+    day: Option<u8>,
+    /// Run every registered day
+    #[arg(short, long)]
+    all: bool,
+    /// Only run this part (1 or 2)
+    #[arg(short, long)]
+    part: Option<Part>,
+    /// Read the input from this file instead of input/DD
+    #[arg(short, long, conflicts_with_all = ["example", "all", "verify", "record"])]
+    input: Option<PathBuf>,
+    /// Read the input from examples/DD/N instead of input/DD
+    #[arg(short, long, value_name = "N")]
+    example: Option<usize>,
+    /// Ignore input that is left over after parsing instead of failing
+    #[arg(long)]
+    lenient: bool,
+    /// Check the answers against the answers file instead of printing them
+    #[arg(long)]
+    verify: bool,
+    /// Print one JSON record per answer instead of text
+    #[arg(long, conflicts_with_all = ["verify", "bench"])]
+    json: bool,
+    /// Save the answers to the answers file
+    #[arg(long, conflicts_with = "verify")]
+    record: bool,
+    /// The file with expected answers
+    #[arg(long, default_value = "answers.txt")]
+    answers: PathBuf,
+    /// Time parsing and each part over N runs and report the min/median/max
+    #[arg(long, value_name = "N", conflicts_with_all = ["verify", "record"])]
+    bench: Option<usize>,
+    /// Save the benchmark results to this file
+    #[arg(long, value_name = "PATH", requires = "bench")]
+    save_bench: Option<PathBuf>,
+    /// Compare the benchmark results to a file saved with --save-bench
+    #[arg(long, value_name = "PATH", requires = "bench")]
+    baseline: Option<PathBuf>,
+    /// How much slower (in percent) the median may get before it counts as a regression
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+    /// Generate a random input instead of solving, writing it to examples/DD/N with --example
+    #[arg(long, requires = "day", conflicts_with_all = ["input", "verify", "record", "json", "bench"])]
+    generate: bool,
+    /// The seed for --generate, chosen at random (and printed) if not given
+    #[arg(long, requires = "generate")]
+    seed: Option<u64>,
+    /// How big an input to generate, usually the number of lines
+    #[arg(long, default_value = "10", requires = "generate")]
+    size: NonZeroUsize,
+}
+
+impl Args {
+    fn input_file(&self) -> InputFile {
+        self.example.map_or(InputFile::Real, InputFile::Example)
+    }
+
+    fn strictness(&self) -> Strictness {
+        if self.lenient {
+            Strictness::Lenient
+        } else {
+            Strictness::Strict
+        }
+    }
+
+    // The file to read for day, or None if it should be skipped
+    fn path(&self, day: &Day) -> Option<PathBuf> {
+        let path = self
+            .input
+            .clone()
+            .unwrap_or_else(|| day.path(self.input_file()));
+        if self.all && !path.exists() {
+            eprintln!(
+                "Skipping day {:02}: {} not found",
+                day.number,
+                path.display()
+            );
+            None
+        } else {
+            Some(path)
+        }
+    }
+}
+
+fn read(path: &Path) -> Result<String, Box<dyn Error>> {
+    fs::read_to_string(path).map_err(|e| format!("Could not read {}: {e}", path.display()).into())
+}
+
+// The answer to one part, along with how it was found
+struct Solved {
+    part: Part,
+    answer: String,
+    parse_time: Duration,
+    solve_time: Duration,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Solved {
+    fn to_json(&self, day: u8, path: &Path) -> serde_json::Value {
+        json!({
+            "day": day,
+            "part": self.part.number(),
+            "input": path.display().to_string(),
+            "answer": self.answer,
+            "timings": {
+                "parse_ns": self.parse_time.as_nanos() as u64,
+                "solve_ns": self.solve_time.as_nanos() as u64,
+            },
+            "diagnostics": self.diagnostics.iter().map(Diagnostic::to_json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+fn solve(
+    day: &Day,
+    path: &Path,
+    parts: &[Part],
+    strictness: Strictness,
+) -> Result<Vec<Solved>, Box<dyn Error>> {
+    let text = read(path)?;
+    let (parsed, parse_stats) = measure(1, || (day.parse)(&text, strictness))?;
+    // Anything reported while parsing belongs to every part
+    let parse_diagnostics = diagnostics::take();
+    parts
+        .iter()
+        .map(|&part| {
+            let (answer, stats) = measure(1, || parsed.solve(part))?;
+            let mut diagnostics = parse_diagnostics.clone();
+            diagnostics.extend(diagnostics::take());
+            Ok(Solved {
+                part,
+                answer,
+                parse_time: parse_stats.median,
+                solve_time: stats.median,
+                diagnostics,
+            })
+        })
+        .collect()
+}
+
+fn run(args: &Args, selected: &[&Day], parts: &[Part]) -> Result<(), Box<dyn Error>> {
+    let mut answers = if args.record {
+        Answers::load(&args.answers)?
+    } else {
+        Answers::default()
+    };
+    let input = args.input_file();
+
+    let mut failed = 0;
+    for &day in selected {
+        let Some(path) = args.path(day) else {
+            continue;
+        };
+        match solve(day, &path, parts, args.strictness()) {
+            Ok(solved) => {
+                for solved in solved {
+                    if args.json {
+                        println!("{}", solved.to_json(day.number, &path));
+                    } else {
+                        println!(
+                            "Day {:02}, part {}: {}",
+                            day.number, solved.part, solved.answer
+                        );
+                    }
+                    let key = Key {
+                        day: day.number,
+                        input,
+                        part: solved.part,
+                    };
+                    answers.insert(key, solved.answer);
+                }
+            }
+            Err(e) if args.json => {
+                let record = json!({ "day": day.number, "error": e.to_string() });
+                println!("{record}");
+                failed += 1;
+            }
+            Err(e) if args.all => {
+                eprintln!("Day {:02} failed: {e}", day.number);
+                failed += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    if args.record {
+        answers.save(&args.answers)?;
+    }
+    if failed > 0 {
+        Err(format!("{failed} days failed").into())
+    } else {
+        Ok(())
+    }
+}
+
+fn verify(args: &Args, selected: &[&Day], parts: &[Part]) -> Result<(), Box<dyn Error>> {
+    let answers = Answers::load(&args.answers)?;
+
+    // Group the expected answers by input so that each input is only parsed once
+    let mut expected: BTreeMap<(u8, InputFile), Vec<(Part, &str)>> = BTreeMap::new();
+    for (key, answer) in answers.iter() {
+        if selected.iter().any(|d| d.number == key.day)
+            && parts.contains(&key.part)
+            && args
+                .example
+                .is_none_or(|n| key.input == InputFile::Example(n))
+        {
+            expected
+                .entry((key.day, key.input))
+                .or_default()
+                .push((key.part, answer));
+        }
+    }
+    if expected.is_empty() {
+        return Err(format!("No expected answers in {}", args.answers.display()).into());
+    }
+
+    println!("{:<4} {:<5} {:<11} Result", "Day", "Part", "Input");
+    let mut failed = 0;
+    for ((number, input), expected) in expected {
+        let day = days::get(number).ok_or(format!("Day {number} is not registered"))?;
+        let parts: Vec<Part> = expected.iter().map(|&(p, _)| p).collect();
+        let solved = solve(day, &day.path(input), &parts, args.strictness());
+        for (part, expected) in expected {
+            let actual = match &solved {
+                Ok(solved) => solved
+                    .iter()
+                    .find(|s| s.part == part)
+                    .map(|s| s.answer.clone())
+                    .ok_or_else(|| "not run".to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            let row = format!("{number:02}   {part:<5} {:<11}", input.to_string());
+            match actual {
+                Ok(actual) if actual == expected => println!("{row} pass"),
+                Ok(actual) => {
+                    failed += 1;
+                    println!("{row} FAIL");
+                    println!("    - {expected}");
+                    println!("    + {actual}");
+                }
+                Err(e) => {
+                    failed += 1;
+                    println!("{row} ERROR");
+                    println!("    {e}");
+                }
+            }
+        }
+    }
+
+    if failed > 0 {
+        Err(format!("{failed} answers did not match").into())
+    } else {
+        Ok(())
+    }
+}
+
+fn report(day: u8, phase: Phase, stats: &Stats, baseline: Option<&Stats>, threshold: f64) -> bool {
+    let Stats { min, median, max } = stats;
+    print!("{day:02}   {phase:<6} {min:>12.2?} {median:>12.2?} {max:>12.2?}");
+    let Some(baseline) = baseline else {
+        println!();
+        return false;
+    };
+    let change = 100.0 * (median.as_secs_f64() / baseline.median.as_secs_f64() - 1.0);
+    let regression = change > threshold;
+    println!(
+        " {:>12.2?} {change:>+8.1}%{}",
+        baseline.median,
+        if regression { " REGRESSION" } else { "" }
+    );
+    regression
+}
+
+fn benchmark(
+    args: &Args,
+    selected: &[&Day],
+    parts: &[Part],
+    n: usize,
+) -> Result<(), Box<dyn Error>> {
+    let baseline = args.baseline.as_deref().map(Results::load).transpose()?;
+    let mut results = Results::default();
+    let input = args.input_file();
+
+    print!(
+        "{:<4} {:<6} {:>12} {:>12} {:>12}",
+        "Day", "Phase", "Min", "Median", "Max"
+    );
+    if baseline.is_some() {
+        print!(" {:>12} {:>9}", "Baseline", "Change");
+    }
+    println!();
+
+    let mut regressions = 0;
+    for &day in selected {
+        let Some(path) = args.path(day) else {
+            continue;
+        };
+        let text = read(&path)?;
+        let (parsed, stats) = measure(n, || (day.parse)(&text, args.strictness()))?;
+        let timings = std::iter::once(Ok((Phase::Parse, stats)));
+        let timings = timings.chain(parts.iter().map(|&part| {
+            let (_, stats) = measure(n, || parsed.solve(part))?;
+            Ok::<_, Box<dyn Error>>((Phase::Solve(part), stats))
+        }));
+        for timing in timings {
+            let (phase, stats) = timing?;
+            diagnostics::take();
+            let key = bench::Key {
+                day: day.number,
+                input,
+                phase,
+            };
+            let base = baseline.as_ref().and_then(|b| b.get(&key));
+            if report(day.number, phase, &stats, base, args.threshold) {
+                regressions += 1;
+            }
+            results.insert(key, stats);
+        }
+    }
+
+    if let Some(path) = &args.save_bench {
+        results.save(path)?;
+    }
+    if regressions > 0 {
+        Err(format!(
+            "{regressions} phases got more than {}% slower",
+            args.threshold
+        )
+        .into())
+    } else {
+        Ok(())
+    }
+}
+
+fn generate(args: &Args, day: &Day) -> Result<(), Box<dyn Error>> {
+    let seed = args.seed.unwrap_or_else(|| {
+        let seed = rand::random();
+        eprintln!("Using seed {seed}");
+        seed
+    });
+    let text = (day.generate)(&mut generate::rng(seed), args.size.get());
+    match args.example {
+        Some(n) => {
+            let path = day.path(InputFile::Example(n));
+            generate::write(&path, &text)?;
+            // No answers are known yet, but with an empty list the example tests still parse it
+            generate::write(&path.with_extension("expected"), "")?;
+            eprintln!("Wrote {}", path.display());
+        }
+        None => print!("{text}"),
+    }
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Args::parse();
+
+    let selected: Vec<&Day> = match args.day {
+        Some(d) => vec![days::get(d).ok_or(format!("Day {d} is not registered"))?],
+        None => DAYS.iter().collect(),
+    };
+    let parts = match args.part {
+        Some(p) => vec![p],
+        None => Part::ALL.to_vec(),
+    };
+
+    // Diagnostics would only get in the way of the tables, so they are only printed when running
+    diagnostics::capture(args.json || args.verify || args.bench.is_some());
+    if args.generate {
+        generate(&args, selected[0])
+    } else if args.verify {
+        verify(&args, &selected, &parts)
+    } else if let Some(n) = args.bench {
+        benchmark(&args, &selected, &parts, n)
+    } else {
+        run(&args, &selected, &parts)
+    }
+}
@@ -1,16 +1,47 @@
+// Template for a new day. Move DayDD and its impl to src/days/dayDD.rs, register it in
+// src/days.rs so the aoc runner can find it, add a generator for its input to src/generate.rs, and
+// keep main here as src/bin/DD.rs.
 use std::error::Error;
 use std::io::prelude::*;
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let stdin = std::io::stdin();
-    let stdin = stdin.lock();
+use aoclib::parse::Strictness;
+use aoclib::solution::Solution;
+
+pub struct DayDD;
+
+impl Solution for DayDD {
+    type Input = Vec<String>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse_with(input: &str, _: Strictness) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part_one(lines: &Self::Input) -> Result<Self::PartOne, Box<dyn Error>> {
+        // BODY
+        Ok(lines.len())
+    }
 
-    let mut answer = 0;
-    for line in stdin.lines() {
-        let line = line?;
+    fn part_two(_lines: &Self::Input) -> Result<Self::PartTwo, Box<dyn Error>> {
         // BODY
+        Ok(0)
     }
-    println!("The answer is: {}", answer);
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let stdin = std::io::stdin();
+    let mut stdin = stdin.lock();
+    let mut input = String::new();
+
+    stdin.read_to_string(&mut input)?;
+    let parsed = DayDD::parse(&input)?;
+
+    let answer = DayDD::part_one(&parsed)?;
+    println!("The answer is: {answer}");
+
+    let answer = DayDD::part_two(&parsed)?;
+    println!("The answer is: {answer}");
 
-    return Ok(());
+    Ok(())
 }
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use crate::answers::InputFile;
+use crate::generate::{self, GenerateFn};
+use crate::solution::{parse_dyn, ParseFn};
+
+// A registered day: its number, a way to parse its input into something that can be solved, and a
+// generator of random inputs
+pub struct Day {
+    pub number: u8,
+    pub parse: ParseFn,
+    pub generate: GenerateFn,
+}
+
+impl Day {
+    // input/DD for the real puzzle input, examples/DD/N for the n-th example
+    pub fn path(&self, input: InputFile) -> PathBuf {
+        match input {
+            InputFile::Real => PathBuf::from(format!("input/{:02}", self.number)),
+            InputFile::Example(n) => PathBuf::from(format!("examples/{:02}/{n}", self.number)),
+        }
+    }
+}
+
+// Declare the module of each day and add it to DAYS, along with the generator of the same name in
+// crate::generate. Adding a new day means adding a line here.
+macro_rules! days {
+    ($($number:literal => $module:ident::$solution:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        pub const DAYS: &[Day] = &[
+            $(Day {
+                number: $number,
+                parse: parse_dyn::<$module::$solution>,
+                generate: generate::$module,
+            },)*
+        ];
+    };
+}
+
+days! {
+    1 => day01::Day01,
+    2 => day02::Day02,
+    3 => day03::Day03,
+    4 => day04::Day04,
+    5 => day05::Day05,
+    6 => day06::Day06,
+    7 => day07::Day07,
+    8 => day08::Day08,
+    9 => day09::Day09,
+    10 => day10::Day10,
+    11 => day11::Day11,
+    12 => day12::Day12,
+}
+
+pub fn get(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
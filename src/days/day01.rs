@@ -0,0 +1,55 @@
+use std::error::Error;
+
+use nom::{
+    branch::alt,
+    character::complete::{char, line_ending, u32},
+    combinator::map,
+    multi::separated_list1,
+    sequence::preceded,
+    Parser,
+};
+
+use crate::dial::Dial;
+use crate::parse::{self, IResult, Strictness};
+use crate::solution::Solution;
+
+pub struct Day01;
+
+const SIZE: u64 = 100;
+const START: u64 = 50;
+
+// The direction gives the sign, so the amounts are unsigned
+pub fn parse_code(input: &str) -> IResult<&str, Vec<i64>> {
+    separated_list1(
+        line_ending,
+        alt((
+            map(preceded(char('R'), u32), i64::from),
+            map(preceded(char('L'), u32), |s| -i64::from(s)),
+        )),
+    )
+    .parse(input)
+}
+
+impl Solution for Day01 {
+    type Input = Vec<i64>;
+    type PartOne = usize;
+    type PartTwo = u64;
+
+    fn parse_with(input: &str, strictness: Strictness) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse::run_with(parse_code, input, strictness)?)
+    }
+
+    fn part_one(rots: &Self::Input) -> Result<Self::PartOne, Box<dyn Error>> {
+        Ok(Dial::new(SIZE, START)
+            .rotations(rots.iter().copied())
+            .filter(|t| t.landed_on_zero)
+            .count())
+    }
+
+    fn part_two(rots: &Self::Input) -> Result<Self::PartTwo, Box<dyn Error>> {
+        Ok(Dial::new(SIZE, START)
+            .rotations(rots.iter().copied())
+            .map(|t| t.clicks_on_zero)
+            .sum())
+    }
+}
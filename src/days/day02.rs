@@ -0,0 +1,160 @@
+use std::error::Error;
+
+use nom::{
+    character::complete::{char, u64},
+    multi::separated_list1,
+    sequence::separated_pair,
+    Parser,
+};
+
+use num::{PrimInt, Unsigned};
+
+use crate::parse::{self, IResult, Strictness};
+use crate::solution::Solution;
+
+pub struct Day02;
+
+fn span(input: &str) -> IResult<&str, (u64, u64)> {
+    separated_pair(u64, char('-'), u64).parse(input)
+}
+
+pub fn parse_ids(input: &str) -> IResult<&str, Vec<(u64, u64)>> {
+    separated_list1(char(','), span).parse(input)
+}
+
+fn ten<T: PrimInt>() -> T {
+    T::from(10).expect("every integer type holds 10")
+}
+
+// The number of decimal digits in n, counting 0 as one digit
+fn digits<T: PrimInt>(mut n: T) -> u32 {
+    let mut d = 1;
+    while n >= ten() {
+        n = n / ten();
+        d += 1;
+    }
+    d
+}
+
+// 10^q, if it fits in a T
+fn power<T: PrimInt>(q: u32) -> Option<T> {
+    num::checked_pow(ten(), q as usize)
+}
+
+// The Möbius function: 0 if n has a square factor, otherwise -1 or 1 for an odd or even number
+// of prime factors
+pub fn mobius(mut n: u32) -> i32 {
+    let mut sign = 1;
+    let mut p = 2;
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
+            }
+            sign = -sign;
+        }
+        p += 1;
+    }
+    if n > 1 {
+        -sign
+    } else {
+        sign
+    }
+}
+
+// first + (first + 1) + ... + last, if it fits in a T. Every partial product is at most the
+// result, so the checked arithmetic only fails if the result doesn't fit.
+fn sum_between<T: PrimInt + Unsigned>(first: T, last: T) -> Option<T> {
+    let count = last - first + T::one();
+    let two = T::one() + T::one();
+    // 0 + 1 + ... + (count - 1), halving whichever factor is even
+    let triangle = if (count % two).is_zero() {
+        (count / two).checked_mul(&(count - T::one()))
+    } else {
+        count.checked_mul(&((count - T::one()) / two))
+    }?;
+    count.checked_mul(&first)?.checked_add(&triangle)
+}
+
+// Sum the invalid IDs between start and end, where an ID is invalid iff its decimal
+// representation is an n-fold repetition. Returns None if the sum doesn't fit in a T.
+pub fn sum_invalids<T: PrimInt + Unsigned>(start: T, end: T, n: u32) -> Option<T> {
+    let mut total = T::zero();
+    if n == 0 || start > end {
+        return Some(total);
+    }
+    // Invalid IDs with d digits are a b-digit block times sep = 10^0 + 10^b + ... + 10^((n-1)b)
+    for d in (n..=digits(end)).step_by(n as usize) {
+        if d < digits(start) {
+            continue;
+        }
+        let b = d / n;
+        // d <= digits(end), so sep < 10^d fits, and so does the smallest block 10^(b-1)
+        let sep = (0..n)
+            .map(|i| power::<T>(i * b))
+            .try_fold(T::zero(), |sum, x| sum.checked_add(&x?))?;
+        let smallest = power::<T>(b - 1)?;
+        // 10^b doesn't fit in the widest types when n is 1
+        let largest = power::<T>(b).map_or(T::max_value(), |p| p - T::one());
+        let rounded_up = start / sep
+            + if (start % sep).is_zero() {
+                T::zero()
+            } else {
+                T::one()
+            };
+        let first = rounded_up.max(smallest);
+        let last = (end / sep).min(largest);
+        if first <= last {
+            total = total.checked_add(&sum_between(first, last)?.checked_mul(&sep)?)?;
+        }
+    }
+    Some(total)
+}
+
+// Sum the IDs between start and end that are an n-fold repetition for any n >= 2. An ID with
+// d digits is one iff it is a p-fold repetition for some prime p dividing d, and the p-fold and
+// q-fold repetitions that are both are the pq-fold ones. So by inclusion-exclusion over the
+// square-free repetition counts, an n-fold sum is counted with sign -μ(n). Returns None if the
+// sum, or the sum of the positive terms, doesn't fit in a T.
+pub fn sum_repetitions<T: PrimInt + Unsigned>(start: T, end: T) -> Option<T> {
+    let (mut added, mut removed) = (T::zero(), T::zero());
+    for n in 2..=digits(end) {
+        match mobius(n) {
+            -1 => added = added.checked_add(&sum_invalids(start, end, n)?)?,
+            1 => removed = removed.checked_add(&sum_invalids(start, end, n)?)?,
+            _ => {}
+        }
+    }
+    Some(added - removed)
+}
+
+// The sum of f over all spans
+fn sum_for(
+    spans: &[(u64, u64)],
+    f: impl Fn(u64, u64) -> Option<u64>,
+) -> Result<u64, Box<dyn Error>> {
+    spans
+        .iter()
+        .map(|&(start, end)| f(start, end))
+        .try_fold(0_u64, |sum, x| sum.checked_add(x?))
+        .ok_or_else(|| "The sum of the invalid IDs doesn't fit in a u64".into())
+}
+
+impl Solution for Day02 {
+    type Input = Vec<(u64, u64)>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse_with(input: &str, strictness: Strictness) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse::run_with(parse_ids, input, strictness)?)
+    }
+
+    fn part_one(spans: &Self::Input) -> Result<Self::PartOne, Box<dyn Error>> {
+        sum_for(spans, |start, end| sum_invalids(start, end, 2))
+    }
+
+    fn part_two(spans: &Self::Input) -> Result<Self::PartTwo, Box<dyn Error>> {
+        sum_for(spans, sum_repetitions)
+    }
+}
@@ -0,0 +1,52 @@
+use std::error::Error;
+
+use itertools::Itertools;
+
+use nom::{
+    character::complete::{anychar, line_ending, u32},
+    combinator::{map_parser, recognize},
+    multi::{many1, separated_list1},
+    Parser,
+};
+
+use crate::parse::{self, IResult, Strictness};
+use crate::solution::Solution;
+
+pub struct Day03;
+
+pub fn parse_batteries(input: &str) -> IResult<&str, Vec<Vec<u32>>> {
+    separated_list1(line_ending, many1(map_parser(recognize(anychar), u32))).parse(input)
+}
+
+pub fn max_joltage(bats: &[u32], n: u32) -> Option<u64> {
+    let mut joltage: u64 = 0;
+    let mut pos = 0;
+    for d in (0..n).rev() {
+        let digit = *(bats.iter().dropping(pos).dropping_back(d as usize).max()?);
+        pos += bats.iter().dropping(pos).position(|&x| x == digit)? + 1;
+        joltage += 10_u64.pow(d) * (digit as u64);
+    }
+    Some(joltage)
+}
+
+pub fn total_joltage(bats: &[Vec<u32>], n: u32) -> u64 {
+    bats.iter().filter_map(|v| max_joltage(v, n)).sum()
+}
+
+impl Solution for Day03 {
+    type Input = Vec<Vec<u32>>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse_with(input: &str, strictness: Strictness) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse::run_with(parse_batteries, input, strictness)?)
+    }
+
+    fn part_one(bats: &Self::Input) -> Result<Self::PartOne, Box<dyn Error>> {
+        Ok(total_joltage(bats, 2))
+    }
+
+    fn part_two(bats: &Self::Input) -> Result<Self::PartTwo, Box<dyn Error>> {
+        Ok(total_joltage(bats, 12))
+    }
+}
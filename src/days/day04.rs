@@ -0,0 +1,76 @@
+use std::error::Error;
+
+use serde_json::json;
+
+use crate::diagnostics;
+use crate::grid::{Grid, UPoint};
+use crate::parse::Strictness;
+use crate::solution::Solution;
+
+pub struct Day04;
+
+// true where there is a roll of paper
+pub fn parse_rolls(input: &str) -> Result<Grid<bool>, Box<dyn Error>> {
+    Ok(Grid::parse(input, |c| match c {
+        '@' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })?)
+}
+
+// The rolls with fewer than four rolls around them
+pub fn accessible(grid: &Grid<bool>) -> Vec<UPoint> {
+    grid.cells()
+        .filter(|&(p, &roll)| roll && grid.diagonal_neighbors(p).filter(|&q| grid[q]).count() < 4)
+        .map(|(p, _)| p)
+        .collect()
+}
+
+impl Solution for Day04 {
+    type Input = Grid<bool>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse_with(input: &str, _: Strictness) -> Result<Self::Input, Box<dyn Error>> {
+        parse_rolls(input)
+    }
+
+    fn part_one(grid: &Self::Input) -> Result<Self::PartOne, Box<dyn Error>> {
+        Ok(accessible(grid).len())
+    }
+
+    fn part_two(grid: &Self::Input) -> Result<Self::PartTwo, Box<dyn Error>> {
+        let mut grid = grid.clone();
+        // The number of rolls around each cell, kept up to date as rolls are removed
+        let mut around = Grid::from_fn(grid.width(), grid.height(), |p| {
+            grid.diagonal_neighbors(p).filter(|&q| grid[q]).count()
+        });
+        let mut stage = accessible(&grid);
+        let mut n_accessible = 0;
+        for n in 0.. {
+            if stage.is_empty() {
+                break;
+            }
+            n_accessible += stage.len();
+            stage.iter().for_each(|&p| grid[p] = false);
+            // Only the neighbors of removed rolls can become accessible, and each does so exactly
+            // when the rolls around it drop from four to three
+            let mut next = Vec::new();
+            for &p in &stage {
+                for q in grid.diagonal_neighbors(p) {
+                    around[q] -= 1;
+                    if grid[q] && around[q] == 3 {
+                        next.push(q);
+                    }
+                }
+            }
+            stage = next;
+            diagnostics::report(
+                "stage",
+                json!({ "stage": n, "accessible": n_accessible }),
+                format_args!("The number of accessible paper rolls (stage {n}) is {n_accessible}"),
+            );
+        }
+        Ok(n_accessible)
+    }
+}
@@ -0,0 +1,95 @@
+use std::cmp::max;
+use std::error::Error;
+
+use nom::{
+    character::complete::{char, line_ending, u64},
+    combinator::verify,
+    error::context,
+    multi::{many1, separated_list1},
+    sequence::separated_pair,
+    Parser,
+};
+
+use crate::parse::{self, IResult, Strictness};
+use crate::solution::Solution;
+
+pub struct Day05;
+
+pub type IngredientRange = (u64, u64);
+
+fn id_range(input: &str) -> IResult<&str, IngredientRange> {
+    context(
+        "a range whose start is at most its end",
+        verify(separated_pair(u64, char('-'), u64), |(lo, hi)| lo <= hi),
+    )
+    .parse(input)
+}
+
+fn ingredients(input: &str) -> IResult<&str, Vec<u64>> {
+    separated_list1(line_ending, u64).parse(input)
+}
+
+pub fn parse_input(input: &str) -> IResult<&str, (Vec<IngredientRange>, Vec<u64>)> {
+    separated_pair(
+        separated_list1(line_ending, id_range),
+        many1(line_ending),
+        ingredients,
+    )
+    .parse(input)
+}
+
+pub fn n_fresh(ranges: &[IngredientRange], ingredients: &[u64]) -> usize {
+    ingredients
+        .iter()
+        .filter(|&i| ranges.iter().any(|&(lo, hi)| lo <= *i && *i <= hi))
+        .count()
+}
+
+pub fn overlap((_, a_hi): IngredientRange, (b_lo, _): IngredientRange) -> bool {
+    b_lo <= a_hi
+}
+
+pub fn merge_ranges(ranges: &[IngredientRange]) -> Vec<IngredientRange> {
+    let mut out: Vec<IngredientRange> = vec![];
+
+    let mut sorted_ranges = ranges.to_vec();
+    sorted_ranges.sort_by_key(|&(lo, _)| lo);
+
+    for r in sorted_ranges.into_iter() {
+        match out.last_mut() {
+            None => out.push(r),
+            Some(p) => {
+                if overlap(*p, r) {
+                    *p = (p.0, max(r.1, p.1));
+                } else {
+                    out.push(r)
+                }
+            }
+        }
+    }
+    out
+}
+
+impl Solution for Day05 {
+    type Input = (Vec<IngredientRange>, Vec<u64>);
+    type PartOne = usize;
+    type PartTwo = u64;
+
+    fn parse_with(input: &str, strictness: Strictness) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse::run_with(parse_input, input, strictness)?)
+    }
+
+    fn part_one((ranges, ingredients): &Self::Input) -> Result<Self::PartOne, Box<dyn Error>> {
+        Ok(n_fresh(ranges, ingredients))
+    }
+
+    fn part_two((ranges, _): &Self::Input) -> Result<Self::PartTwo, Box<dyn Error>> {
+        // Computed in u128, since the range 0-18446744073709551615 alone has 2^64 IDs
+        let merged = merge_ranges(ranges);
+        let fresh: u128 = merged
+            .into_iter()
+            .map(|(lo, hi)| (hi - lo) as u128 + 1)
+            .sum();
+        Ok(u64::try_from(fresh).map_err(|_| "There are too many fresh IDs to count")?)
+    }
+}
@@ -0,0 +1,119 @@
+use std::error::Error;
+
+use nom::{
+    branch::alt,
+    character::complete::{char, line_ending, multispace1, space0, space1, u64},
+    combinator::value,
+    multi::separated_list1,
+    sequence::{delimited, separated_pair},
+    Parser,
+};
+
+use crate::grid::{Symmetry, Transformable};
+use crate::parse::{self, IResult, Strictness};
+use crate::solution::Solution;
+
+pub struct Day06;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Operation {
+    Add,
+    Multiply,
+}
+
+// The worksheet read row by row, and read column by column from the right
+pub struct Worksheet {
+    pub operands: Vec<Vec<u64>>,
+    pub operators: Vec<Operation>,
+    pub transposed: Vec<(Vec<u64>, Operation)>,
+}
+
+fn parse_operator(input: &str) -> IResult<&str, Operation> {
+    alt((
+        value(Operation::Add, char('+')),
+        value(Operation::Multiply, char('*')),
+    ))
+    .parse(input)
+}
+
+fn parse_operands(input: &str) -> IResult<&str, Vec<u64>> {
+    delimited(space0, separated_list1(space1, u64), space0).parse(input)
+}
+
+pub fn parse_input(input: &str) -> IResult<&str, (Vec<Vec<u64>>, Vec<Operation>)> {
+    separated_pair(
+        separated_list1(line_ending, parse_operands),
+        line_ending,
+        delimited(space0, separated_list1(space1, parse_operator), space0),
+    )
+    .parse(input)
+}
+
+pub fn parse_transposed(input: &str) -> IResult<&str, Vec<(Vec<u64>, Operation)>> {
+    separated_list1(
+        multispace1,
+        separated_pair(
+            separated_list1(line_ending, delimited(space0, u64, space0)),
+            space0,
+            parse_operator,
+        ),
+    )
+    .parse(input)
+}
+
+// The result of a problem, or None if it doesn't fit in a u64
+fn apply(op: Operation, mut values: impl Iterator<Item = u64>) -> Option<u64> {
+    match op {
+        Operation::Add => values.try_fold(0, u64::checked_add),
+        Operation::Multiply => values.try_fold(1, u64::checked_mul),
+    }
+}
+
+fn grand_total(results: impl Iterator<Item = Option<u64>>) -> Result<u64, Box<dyn Error>> {
+    results
+        .map(|r| r.ok_or("A problem's result doesn't fit in a u64"))
+        .try_fold(0_u64, |sum, r| {
+            sum.checked_add(r?)
+                .ok_or("The grand total doesn't fit in a u64")
+        })
+        .map_err(Into::into)
+}
+
+impl Solution for Day06 {
+    type Input = Worksheet;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse_with(input: &str, strictness: Strictness) -> Result<Self::Input, Box<dyn Error>> {
+        let (operands, operators) = parse::run_with(parse_input, input, strictness)?;
+        // Reading the columns from the right is turning the worksheet counterclockwise
+        let transposed = input.to_string().transform(Symmetry::Rotate270);
+        let transposed = parse::run_with(parse_transposed, &transposed, strictness)
+            .map_err(|e| format!("In the transposed worksheet: {e}"))?;
+        Ok(Worksheet {
+            operands,
+            operators,
+            transposed,
+        })
+    }
+
+    fn part_one(sheet: &Self::Input) -> Result<Self::PartOne, Box<dyn Error>> {
+        let mut columns: Vec<_> = sheet.operands.iter().map(|r| r.iter()).collect();
+
+        grand_total(
+            sheet
+                .operators
+                .iter()
+                .map(|&o| apply(o, columns.iter_mut().filter_map(Iterator::next).copied())),
+        )
+    }
+
+    fn part_two(sheet: &Self::Input) -> Result<Self::PartTwo, Box<dyn Error>> {
+        grand_total(
+            sheet
+                .transposed
+                .iter()
+                .map(|(vs, op)| apply(*op, vs.iter().copied())),
+        )
+    }
+}
@@ -0,0 +1,95 @@
+use std::error::Error;
+
+use crate::parse::Strictness;
+use crate::solution::Solution;
+
+pub struct Day07;
+
+pub struct Manifold {
+    pub width: usize,
+    pub start: usize,
+    pub rows: Vec<String>,
+}
+
+pub fn parse_manifold(input: &str) -> Result<Manifold, Box<dyn Error>> {
+    let mut ls = input.lines();
+    let top = ls.next().ok_or("Invalid input: no first line")?;
+    let start = top
+        .chars()
+        .position(|c| c == 'S')
+        .ok_or("Invalid input: no start")?;
+    let width = top.chars().count();
+    let rows: Vec<String> = ls.map(String::from).collect();
+    // propagate only keeps track of the columns of the first line
+    if let Some((n, row)) = rows
+        .iter()
+        .enumerate()
+        .find(|(_, r)| r.chars().count() > width)
+    {
+        return Err(format!(
+            "Invalid input: line {} is {} wide, but the first line is only {width}",
+            n + 2,
+            row.chars().count()
+        )
+        .into());
+    }
+    Ok(Manifold { width, start, rows })
+}
+
+// Send the beam down the manifold, returning the number of splits and the number of timelines
+// ending in each column. Fails if there are too many timelines to count.
+pub fn propagate(manifold: &Manifold) -> Result<(usize, Vec<usize>), Box<dyn Error>> {
+    let width = manifold.width;
+    let mut splits = 0;
+    let mut tachyons: Vec<usize> = vec![0; width];
+    let add = |count: &mut usize, n: usize| {
+        *count = count
+            .checked_add(n)
+            .ok_or("There are too many timelines to count")?;
+        Ok::<_, Box<dyn Error>>(())
+    };
+
+    tachyons[manifold.start] = 1;
+    for line in &manifold.rows {
+        let mut new_tachyons = vec![0; width];
+        for (n, c) in line.chars().enumerate() {
+            if c == '^' && tachyons[n] > 0 {
+                splits += 1;
+                new_tachyons[n] = 0;
+                if n > 0 {
+                    add(&mut new_tachyons[n - 1], tachyons[n])?;
+                }
+                if n < width - 1 {
+                    add(&mut new_tachyons[n + 1], tachyons[n])?;
+                }
+            } else {
+                add(&mut new_tachyons[n], tachyons[n])?;
+            }
+        }
+        tachyons = new_tachyons;
+    }
+    Ok((splits, tachyons))
+}
+
+impl Solution for Day07 {
+    type Input = Manifold;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse_with(input: &str, _: Strictness) -> Result<Self::Input, Box<dyn Error>> {
+        parse_manifold(input)
+    }
+
+    fn part_one(manifold: &Self::Input) -> Result<Self::PartOne, Box<dyn Error>> {
+        let (splits, _) = propagate(manifold)?;
+        Ok(splits)
+    }
+
+    fn part_two(manifold: &Self::Input) -> Result<Self::PartTwo, Box<dyn Error>> {
+        let (_, tachyons) = propagate(manifold)?;
+        tachyons
+            .iter()
+            .try_fold(0_usize, |sum, &t| sum.checked_add(t))
+            .ok_or_else(|| "There are too many timelines to count".into())
+    }
+}
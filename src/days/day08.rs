@@ -0,0 +1,93 @@
+use std::cmp::{max, min};
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
+use itertools::Itertools;
+use nom::{character::complete::line_ending, multi::separated_list1, Parser};
+
+use crate::parse::{self, IResult, Strictness};
+use crate::point::{self, Point3};
+use crate::solution::Solution;
+
+pub struct Day08;
+
+pub type Coordinates = Point3<u64>;
+
+const MAX_CONNECTIONS: usize = 1000;
+
+pub fn parse_input(input: &str) -> IResult<&str, Vec<Coordinates>> {
+    separated_list1(line_ending, point::xyz(parse::coordinate)).parse(input)
+}
+
+// Connect the junction boxes closest first. Returns the product of the three largest circuit
+// sizes after max_connections connections, and the product of the x coordinates of the last two
+// boxes that had to be connected to join everything into a single circuit.
+pub fn connect(jbs: &[Coordinates], max_connections: usize) -> (usize, u64) {
+    let mut components: Vec<BTreeSet<Coordinates>> = Vec::new();
+    let mut component_for_jbs = BTreeMap::<Coordinates, usize>::new();
+    for (n, &c) in jbs.iter().enumerate() {
+        let mut b = BTreeSet::new();
+        b.insert(c);
+        components.push(b);
+        component_for_jbs.insert(c, n);
+    }
+
+    // Every pair of junction boxes once
+    let mut edges = jbs.iter().tuple_combinations::<(_, _)>().collect_vec();
+    // Coordinates below 2^31 can't overflow the squared distance
+    edges.sort_by_key(|(a, b)| {
+        a.squared_euclidean(**b)
+            .expect("coordinates are below 2^31")
+    });
+
+    let mut circuit_size: usize = 0;
+    let mut dist: u64 = 0;
+    let mut n_components = components.len();
+    for (n, (a, b)) in edges.iter().enumerate() {
+        if n == max_connections {
+            let mut sizes = components.iter().map(BTreeSet::len).collect_vec();
+            sizes.sort();
+            circuit_size = sizes.iter().rev().take(3).product();
+        }
+
+        let (&ca, &cb) = (
+            component_for_jbs.get(a).unwrap(),
+            component_for_jbs.get(b).unwrap(),
+        );
+        if ca == cb {
+            continue;
+        }
+
+        let (left, right) = components.split_at_mut(max(ca, cb));
+        for &jb in right[0].iter() {
+            left[min(ca, cb)].insert(jb);
+            component_for_jbs.insert(jb, min(ca, cb));
+        }
+        right[0].clear();
+        n_components -= 1;
+        if n_components == 1 {
+            dist = a.x() * b.x()
+        }
+    }
+    (circuit_size, dist)
+}
+
+impl Solution for Day08 {
+    type Input = Vec<Coordinates>;
+    type PartOne = usize;
+    type PartTwo = u64;
+
+    fn parse_with(input: &str, strictness: Strictness) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse::run_with(parse_input, input, strictness)?)
+    }
+
+    fn part_one(jbs: &Self::Input) -> Result<Self::PartOne, Box<dyn Error>> {
+        let (circuit_size, _) = connect(jbs, MAX_CONNECTIONS);
+        Ok(circuit_size)
+    }
+
+    fn part_two(jbs: &Self::Input) -> Result<Self::PartTwo, Box<dyn Error>> {
+        let (_, dist) = connect(jbs, MAX_CONNECTIONS);
+        Ok(dist)
+    }
+}
@@ -0,0 +1,157 @@
+use std::cmp::Reverse;
+use std::error::Error;
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use itertools::Itertools;
+
+use rayon::prelude::*;
+
+use nom::{character::complete::line_ending, multi::separated_list1, Parser};
+
+use serde_json::json;
+
+use crate::diagnostics;
+use crate::geometry::{largest_rectangle, LargestRectangle, RectilinearPolygon};
+use crate::parse::{self, IResult, Strictness};
+use crate::point::{self, Point, Point2};
+use crate::solution::Solution;
+
+pub struct Day09;
+
+pub type Coordinates = Point2<u64>;
+
+pub fn parse_input(input: &str) -> IResult<&str, Vec<Coordinates>> {
+    separated_list1(line_ending, point::xy(parse::coordinate)).parse(input)
+}
+
+// The number of tiles in the rectangle with corners a and b
+pub fn area(a: Coordinates, b: Coordinates) -> u64 {
+    a.abs_diff(b).0.iter().map(|d| d + 1).product()
+}
+
+// The coordinates d for which the segment from c to d is inside, found by binary search since a
+// segment is only inside if every shorter one from c is. c must be one of the sorted coordinates.
+fn reach(coordinates: &[u64], c: u64, inside: impl Fn(u64) -> bool) -> RangeInclusive<u64> {
+    let k = coordinates.partition_point(|&d| d < c);
+    let (before, after) = (&coordinates[..=k], &coordinates[k..]);
+    before[before.partition_point(|&d| !inside(d))]
+        ..=after[after.partition_point(|&d| inside(d)) - 1]
+}
+
+// A red tile, and where the other corner of a rectangle inside the loop can be
+struct Anchor {
+    index: usize,
+    columns: RangeInclusive<u64>,
+    rows: RangeInclusive<u64>,
+    // The largest rectangle with its other corner there
+    bound: u64,
+}
+
+impl Anchor {
+    fn reaches(&self, b: Coordinates) -> bool {
+        self.columns.contains(&b.x()) && self.rows.contains(&b.y())
+    }
+}
+
+// A rectangle is only inside the loop if its edges from each corner are, so the other corner is
+// within how far the loop reaches along the corner's row and column. The red tiles by the largest
+// rectangle that leaves them, largest first.
+fn anchors(polygon: &RectilinearPolygon<u64>, reds: &[Coordinates]) -> Vec<Anchor> {
+    let xs: Vec<u64> = reds.iter().map(|r| r.x()).sorted().dedup().collect();
+    let ys: Vec<u64> = reds.iter().map(|r| r.y()).sorted().dedup().collect();
+    let mut anchors: Vec<_> = reds
+        .par_iter()
+        .enumerate()
+        .map(|(index, &a)| {
+            let columns = reach(&xs, a.x(), |x| polygon.contains_rect(a, Point([x, a.y()])));
+            let rows = reach(&ys, a.y(), |y| polygon.contains_rect(a, Point([a.x(), y])));
+            let mut anchor = Anchor {
+                index,
+                columns,
+                rows,
+                bound: 0,
+            };
+            anchor.bound = reds
+                .iter()
+                .filter(|&&b| anchor.reaches(b))
+                .map(|&b| area(a, b))
+                .max()
+                .unwrap_or(0);
+            anchor
+        })
+        .collect();
+    anchors.sort_by_key(|anchor| Reverse(anchor.bound));
+    anchors
+}
+
+impl Solution for Day09 {
+    type Input = Vec<Coordinates>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse_with(input: &str, strictness: Strictness) -> Result<Self::Input, Box<dyn Error>> {
+        Ok(parse::run_with(parse_input, input, strictness)?)
+    }
+
+    fn part_one(reds: &Self::Input) -> Result<Self::PartOne, Box<dyn Error>> {
+        let LargestRectangle { corners, area } = largest_rectangle(reds)
+            .ok_or("Invalid input: a rectangle needs at least two red tiles")?;
+        diagnostics::report(
+            "corners",
+            json!({ "corners": [corners.0.0, corners.1.0] }),
+            format_args!(
+                "The largest rectangle is from {} to {}",
+                corners.0, corners.1
+            ),
+        );
+        // Coordinates below 2^31 keep the area below 2^62
+        Ok(u64::try_from(area)?)
+    }
+
+    fn part_two(reds: &Self::Input) -> Result<Self::PartTwo, Box<dyn Error>> {
+        // The red and green tiles are the ones the loop through the red tiles covers
+        let polygon =
+            RectilinearPolygon::new(reds.clone()).map_err(|e| format!("Invalid input: {e}"))?;
+        // Every rectangle is at most as large as the bound of either corner, so visit the corners
+        // by their bound and stop when it can't beat the largest rectangle found so far
+        let mut done = vec![false; reds.len()];
+        let mut best: Option<u64> = None;
+        let considered = AtomicUsize::new(0);
+        for anchor in anchors(&polygon, reds) {
+            if best.is_some_and(|best| anchor.bound <= best) {
+                break;
+            }
+            let (i, a) = (anchor.index, reds[anchor.index]);
+            let found = reds
+                .par_iter()
+                .enumerate()
+                .filter(|&(j, &b)| {
+                    !done[j]
+                        && j != i
+                        && anchor.reaches(b)
+                        && best.is_none_or(|best| area(a, b) > best)
+                })
+                .filter(|&(_, &b)| {
+                    considered.fetch_add(1, Ordering::Relaxed);
+                    polygon.contains_rect(a, b)
+                })
+                .map(|(_, &b)| area(a, b))
+                .max();
+            best = best.max(found);
+            done[i] = true;
+        }
+        // The tiles along an edge of the loop always make one
+        let answer = best.ok_or("No rectangle is inside the loop")?;
+
+        let considered = considered.into_inner();
+        let n = reds.len() * reds.len().saturating_sub(1) / 2;
+        diagnostics::report(
+            "considered",
+            json!({ "considered": considered, "squares": n }),
+            format_args!("Considered {considered}/{n} squares"),
+        );
+
+        Ok(answer)
+    }
+}
@@ -0,0 +1,156 @@
+use std::error::Error;
+
+use nom::{
+    branch::alt,
+    character::complete::{char, line_ending, space0, u64, usize},
+    combinator::{map, value},
+    multi::{many1, separated_list1},
+    sequence::delimited,
+    Parser,
+};
+
+use itertools::Itertools;
+
+use microlp::{ComparisonOp, OptimizationDirection, Problem};
+
+use crate::parse::{self, IResult, Strictness};
+use crate::search;
+use crate::solution::Solution;
+
+pub struct Day10;
+
+pub struct Machine {
+    pub lights: Vec<bool>,
+    pub buttons: Vec<Vec<usize>>,
+    pub joltage_reqs: Vec<u64>,
+}
+
+fn parse_lights(input: &str) -> IResult<&str, Vec<bool>> {
+    many1(alt((value(true, char('#')), value(false, char('.'))))).parse(input)
+}
+
+fn parse_button(input: &str) -> IResult<&str, Vec<usize>> {
+    separated_list1(char(','), usize).parse(input)
+}
+
+fn parse_joltage_reqs(input: &str) -> IResult<&str, Vec<u64>> {
+    separated_list1(char(','), u64).parse(input)
+}
+
+fn parse_machine(input: &str) -> IResult<&str, Machine> {
+    map(
+        (
+            delimited(char('['), parse_lights, char(']')),
+            space0,
+            separated_list1(space0, delimited(char('('), parse_button, char(')'))),
+            space0,
+            delimited(char('{'), parse_joltage_reqs, char('}')),
+        ),
+        |(lights, _, buttons, _, joltage_reqs)| Machine {
+            lights,
+            buttons,
+            joltage_reqs,
+        },
+    )
+    .parse(input)
+}
+
+pub fn parse_input(input: &str) -> IResult<&str, Vec<Machine>> {
+    separated_list1(line_ending, parse_machine).parse(input)
+}
+
+pub fn toggle_lights(state: &[bool], button: &[usize]) -> Vec<bool> {
+    let mut out = state.to_vec();
+    for &l in button {
+        out[l] = !out[l];
+    }
+    out
+}
+
+// The fewest presses that turn on the lights, or None if no combination of buttons does
+pub fn bfs_lights(m: &Machine) -> Option<usize> {
+    let off = vec![false; m.lights.len()];
+    let successors = |s: &Vec<bool>| m.buttons.iter().map(|b| toggle_lights(s, b)).collect_vec();
+    search::bfs([off], successors, |s| *s == m.lights).map(|path| path.cost)
+}
+
+// The fewest presses that meet the joltage requirements, or None if they can't be met
+pub fn optimize_joltage(machine: &Machine) -> Option<f64> {
+    let mut p = Problem::new(OptimizationDirection::Minimize);
+    let vars = (0..machine.buttons.len())
+        .map(|_| p.add_integer_var(1.0, (0, i32::MAX)))
+        .collect_vec();
+    for (n, j) in machine.joltage_reqs.iter().enumerate() {
+        let lhs = machine
+            .buttons
+            .iter()
+            .enumerate()
+            .filter_map(|(m, v)| if v.iter().contains(&n) { Some(m) } else { None })
+            .map(|m| (vars[m], 1.0));
+        p.add_constraint(lhs, ComparisonOp::Eq, *j as f64);
+    }
+    p.solve().ok().map(|s| s.objective())
+}
+
+// Buttons can only toggle lights that exist, and there is a joltage requirement for every light
+fn validate(machines: &[Machine]) -> Result<(), Box<dyn Error>> {
+    for (n, m) in machines.iter().enumerate() {
+        let lights = m.lights.len();
+        if let Some(&l) = m.buttons.iter().flatten().find(|&&l| l >= lights) {
+            return Err(format!(
+                "Invalid input: a button of machine {} toggles light {l}, but it only has {lights} lights",
+                n + 1
+            )
+            .into());
+        }
+        if m.joltage_reqs.len() != lights {
+            return Err(format!(
+                "Invalid input: machine {} has {lights} lights but {} joltage requirements",
+                n + 1,
+                m.joltage_reqs.len()
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+impl Solution for Day10 {
+    type Input = Vec<Machine>;
+    type PartOne = usize;
+    type PartTwo = f64;
+
+    fn parse_with(input: &str, strictness: Strictness) -> Result<Self::Input, Box<dyn Error>> {
+        let machines = parse::run_with(parse_input, input, strictness)?;
+        validate(&machines)?;
+        Ok(machines)
+    }
+
+    fn part_one(machines: &Self::Input) -> Result<Self::PartOne, Box<dyn Error>> {
+        machines
+            .iter()
+            .enumerate()
+            .map(|(n, m)| {
+                bfs_lights(m).ok_or(format!(
+                    "The lights of machine {} can't be turned on",
+                    n + 1
+                ))
+            })
+            .sum::<Result<_, _>>()
+            .map_err(Into::into)
+    }
+
+    fn part_two(machines: &Self::Input) -> Result<Self::PartTwo, Box<dyn Error>> {
+        machines
+            .iter()
+            .enumerate()
+            .map(|(n, m)| {
+                optimize_joltage(m).ok_or(format!(
+                    "The joltage requirements of machine {} can't be met",
+                    n + 1
+                ))
+            })
+            .sum::<Result<_, _>>()
+            .map_err(Into::into)
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+
+use nom::{
+    character::complete::{alpha1, char, line_ending, space1},
+    combinator::map,
+    multi::separated_list1,
+    Parser,
+};
+
+use crate::diagnostics;
+use crate::parse::{self, IResult, Strictness};
+use crate::solution::Solution;
+
+pub struct Day11;
+
+pub type Tree = BTreeMap<String, Vec<String>>;
+
+pub fn parse_input(input: &str) -> IResult<&str, Tree> {
+    map(
+        separated_list1(
+            line_ending,
+            map(
+                (alpha1, char(':'), space1, separated_list1(space1, alpha1)),
+                |(name, _, _, nodes): (&str, _, _, Vec<&str>)| {
+                    (
+                        name.to_string(),
+                        nodes.into_iter().map(String::from).collect(),
+                    )
+                },
+            ),
+        ),
+        |v| v.into_iter().collect(),
+    )
+    .parse(input)
+}
+
+pub fn transpose_tree(tree: &Tree) -> Tree {
+    let mut out = Tree::new();
+    for (name, outgoing) in tree.iter() {
+        for o in outgoing {
+            out.entry(o.clone()).or_default().push(name.clone());
+        }
+    }
+    out
+}
+
+// The nodes with a path to start in the transposed tree
+fn reaching<'a>(start: &'a str, tree: &'a Tree) -> BTreeSet<&'a str> {
+    let mut back: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (name, outgoing) in tree {
+        for o in outgoing {
+            back.entry(o).or_default().push(name);
+        }
+    }
+    let mut seen = BTreeSet::from([start]);
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        for &n in back.get(node).into_iter().flatten() {
+            if seen.insert(n) {
+                stack.push(n);
+            }
+        }
+    }
+    seen
+}
+
+// The number of paths to each node, or None while it's still being counted. Only nodes that lead
+// to start are counted, so finding such a node again means that it's part of a cycle on a path.
+fn dfs<'a>(
+    start: &'a str,
+    tree: &'a Tree,
+    live: &BTreeSet<&'a str>,
+    paths: &mut BTreeMap<&'a str, Option<usize>>,
+) -> Result<usize, Box<dyn Error>> {
+    if !live.contains(start) {
+        return Ok(0);
+    }
+    match paths.get(start) {
+        Some(Some(n)) => Ok(*n),
+        Some(None) => Err(format!("Invalid input: {start} is part of a cycle").into()),
+        None => {
+            paths.insert(start, None);
+            let mut p: usize = 0;
+            for node in tree.get(start).into_iter().flatten() {
+                p = p
+                    .checked_add(dfs(node, tree, live, paths)?)
+                    .ok_or("There are too many paths to count")?;
+            }
+            paths.insert(start, Some(p));
+            Ok(p)
+        }
+    }
+}
+
+// Count the paths from start to end in the transposed tree
+pub fn paths_between<'a>(
+    start: &'a str,
+    end: &'a str,
+    tree: &'a Tree,
+) -> Result<usize, Box<dyn Error>> {
+    let live = reaching(start, tree);
+    let mut paths: BTreeMap<&str, Option<usize>> = BTreeMap::new();
+    paths.insert(start, Some(1));
+    dfs(end, tree, &live, &mut paths)
+}
+
+impl Solution for Day11 {
+    type Input = Tree;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse_with(input: &str, strictness: Strictness) -> Result<Self::Input, Box<dyn Error>> {
+        let tree = parse::run_with(parse_input, input, strictness)?;
+        Ok(transpose_tree(&tree))
+    }
+
+    fn part_one(transposed: &Self::Input) -> Result<Self::PartOne, Box<dyn Error>> {
+        paths_between("you", "out", transposed)
+    }
+
+    fn part_two(transposed: &Self::Input) -> Result<Self::PartTwo, Box<dyn Error>> {
+        let svr_to_fft = paths_between("svr", "fft", transposed)?;
+        diagnostics::report(
+            "svr_to_fft",
+            svr_to_fft,
+            format_args!("svr to fft: {svr_to_fft}"),
+        );
+        let svr_to_dac = paths_between("svr", "dac", transposed)?;
+        diagnostics::report(
+            "svr_to_dac",
+            svr_to_dac,
+            format_args!("svr to dac: {svr_to_dac}"),
+        );
+
+        let dac_to_fft = paths_between("dac", "fft", transposed)?;
+        diagnostics::report(
+            "dac_to_fft",
+            dac_to_fft,
+            format_args!("dac to fft: {dac_to_fft}"),
+        );
+        let dac_to_out = paths_between("dac", "out", transposed)?;
+        diagnostics::report(
+            "dac_to_out",
+            dac_to_out,
+            format_args!("dac to out: {dac_to_out}"),
+        );
+
+        let fft_to_dac = paths_between("fft", "dac", transposed)?;
+        diagnostics::report(
+            "fft_to_dac",
+            fft_to_dac,
+            format_args!("fft to dac: {fft_to_dac}"),
+        );
+        let fft_to_out = paths_between("fft", "out", transposed)?;
+        diagnostics::report(
+            "fft_to_out",
+            fft_to_out,
+            format_args!("fft to out: {fft_to_out}"),
+        );
+
+        let product = |a: usize, b: usize, c: usize| a.checked_mul(b)?.checked_mul(c);
+        product(svr_to_fft, fft_to_dac, dac_to_out)
+            .zip(product(svr_to_dac, dac_to_fft, fft_to_out))
+            .and_then(|(a, b)| a.checked_add(b))
+            .ok_or_else(|| "There are too many paths to count".into())
+    }
+}
@@ -0,0 +1,276 @@
+use std::error::Error;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+use nom::{
+    branch::alt,
+    character::complete::{char, line_ending, space1, usize},
+    combinator::{map, value, verify},
+    error::context,
+    multi::separated_list1,
+    sequence::{preceded, separated_pair},
+    Parser,
+};
+
+use itertools::iproduct;
+
+use good_lp::{
+    constraint, default_solver, variable, variables, Solution as LpSolution, SolverModel,
+};
+use ndarray::Array;
+
+use rayon::prelude::*;
+
+use rand::Rng;
+
+use serde_json::json;
+
+use crate::diagnostics;
+use crate::grid::{Symmetry, Transformable};
+use crate::parse::{self, IResult, Strictness};
+use crate::solution::{NoAnswer, Solution};
+
+pub struct Day12;
+
+#[inline]
+fn cell(input: &str) -> IResult<&str, bool> {
+    alt((value(false, char('.')), value(true, char('#')))).parse(input)
+}
+
+#[inline]
+fn row(input: &str) -> IResult<&str, [bool; 3]> {
+    map((cell, cell, cell), |(a, b, c)| [a, b, c]).parse(input)
+}
+pub fn parse_present(input: &str) -> IResult<&str, Present> {
+    preceded(
+        (usize, char(':'), line_ending),
+        map(
+            (row, line_ending, row, line_ending, row, line_ending),
+            |(a, _, b, _, c, _)| Present([a, b, c]),
+        ),
+    )
+    .parse(input)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PackingProblem {
+    pub width: usize,
+    pub height: usize,
+    pub constraints: Vec<usize>,
+}
+// Real regions are at most 50×50, and pack needs memory for every cell times every orientation
+const MAX_AREA: usize = 100_000;
+
+fn region(input: &str) -> IResult<&str, (usize, usize)> {
+    context(
+        "a region of at most 100000 cells",
+        verify(separated_pair(usize, char('x'), usize), |&(w, h)| {
+            w.checked_mul(h).is_some_and(|area| area <= MAX_AREA)
+        }),
+    )
+    .parse(input)
+}
+
+pub fn parse_problem(input: &str) -> IResult<&str, PackingProblem> {
+    map(
+        separated_pair(region, (char(':'), space1), separated_list1(space1, usize)),
+        |((width, height), constraints)| PackingProblem {
+            width,
+            height,
+            constraints,
+        },
+    )
+    .parse(input)
+}
+
+pub fn parse_input(input: &str) -> IResult<&str, (Vec<Present>, Vec<PackingProblem>)> {
+    separated_pair(
+        separated_list1(line_ending, parse_present),
+        line_ending,
+        separated_list1(line_ending, parse_problem),
+    )
+    .parse(input)
+}
+
+// Each present is a 3x3 boolean matrix where true means that cell is covered by the present.
+// Since rotating and flipping is allowed, presents transform like any square matrix.
+#[derive(Hash, Copy, Clone, Eq, PartialEq)]
+pub struct Present(pub [[bool; 3]; 3]);
+
+impl Transformable for Present {
+    fn transform(&self, symmetry: Symmetry) -> Present {
+        Present(self.0.transform(symmetry))
+    }
+}
+
+pub fn pack(
+    presents: &[Present],
+    PackingProblem {
+        height,
+        width,
+        constraints,
+    }: &PackingProblem,
+) -> Result<Box<dyn LpSolution + 'static>, Box<dyn Error + 'static>> {
+    //let mut prob = Problem::new(OptimizationDirection::Minimize);
+    let xmax = width.saturating_sub(2);
+    let ymax = height.saturating_sub(2);
+
+    // The variables in the problem are, for every type of present and every cell, a decision variable whether to place a present of that type with its top left corner there.
+    // In an N×M grid this is only possible if (x, y) < (N-2, M-2) [zero-indexed].
+    // First we rotate and reflect all the presents and count the number of types
+    let mut vars = variables! {};
+    let dihedral: Vec<_> = presents.iter().map(Present::orbit).collect();
+    let n_shapes = dihedral.len();
+    let n_presents = dihedral.iter().map(Vec::len).sum();
+    // Now we can create the variables
+    let bool_var = || variable().integer().min(0).max(1);
+    let placed = Array::from_shape_vec(
+        (n_presents, xmax, ymax),
+        iproduct![0..n_presents, 0..xmax, 0..ymax]
+            .map(|_| vars.add(bool_var()))
+            .collect(),
+    )
+    // This unwrap is safe because we control the shape of the array
+    .unwrap();
+    let mut model = vars.minimise(0.0).using(default_solver);
+    {
+        // workaround for coin-cbc not being *completely* thread safe: there's a race condition in
+        // parsing parameters. so we wait for a random duration
+        model.set_parameter("log", "0");
+        let mut rng = rand::rng();
+        sleep(Duration::from_millis(rng.random_range(0..=100)));
+    }
+    //model.as_inner_mut().set_obj_sense(coin_cbc::Sense::Ignore);
+
+    // The simplest constraints are the usage constraints
+    //     \sum_{j ~ k} placed[j] = presents[k]
+    // where presents[k] is the number of presents of each type to be placed, and
+    //     j ~ k
+    // means that j is a rotation and or reflection of k.
+
+    let mut idx: usize = 0;
+    for (n, class) in dihedral.iter().enumerate() {
+        let lhs = iproduct![idx..(idx + class.len()), 0..xmax, 0..ymax]
+            .map(|vidx| placed[vidx] * 1i32)
+            .sum::<good_lp::Expression>();
+        model.add_constraint(constraint!(lhs == constraints[n] as i32));
+        idx += class.len();
+    }
+    let n_present_constraints = constraints.len();
+
+    // The number of presents covering a cell is
+    //     covered[x][y] = \sum_k (shape[k] * placed[k]) <= 1
+    // where * is convolution (standard interpretation of convolution as sliding a window over an image).
+    let presents: Vec<_> = dihedral.into_iter().flatten().collect();
+    for (x, y) in iproduct![0..*width, 0..*height] {
+        let lhs = iproduct![0..n_presents, 0..3, 0..3]
+            .filter_map(|(k, u, v)| {
+                if u <= x && v <= y && x - u < xmax && y - v < ymax {
+                    Some(placed[(k, x - u, y - v)] * (presents[k].0[u][v] as i32))
+                } else {
+                    None
+                }
+            })
+            .sum::<good_lp::Expression>();
+        model.add_constraint(constraint!(lhs <= 1i32));
+    }
+    let n_packing_constraints = height * width;
+
+    // Report some diagnostics
+    let n_vars = n_presents * xmax * ymax;
+    diagnostics::report(
+        "problem",
+        json!({
+            "width": width,
+            "height": height,
+            "shapes": n_shapes,
+            "orientations": n_presents,
+            "vars": n_vars,
+            "present_constraints": n_present_constraints,
+            "packing_constraints": n_packing_constraints,
+        }),
+        format_args!(
+            "Solving {width}×{height} problem with {n_shapes} present shapes in {n_presents} orientations: {} vars, {} + {} = {} constraints",
+            n_vars,
+            n_present_constraints,
+            n_packing_constraints,
+            n_packing_constraints + n_present_constraints
+        ),
+    );
+    let now = Instant::now();
+    let out = model.solve();
+    let seconds = now.elapsed().as_secs_f32();
+    let feasibility = if out.is_ok() {
+        "feasible"
+    } else {
+        "infeasible"
+    };
+    diagnostics::report(
+        "solved",
+        json!({
+            "width": width,
+            "height": height,
+            "seconds": seconds,
+            "feasible": out.is_ok(),
+        }),
+        format_args!("Solved a {width}×{height} problem in {seconds:6.2} s: {feasibility}"),
+    );
+    out.map(|s| Box::new(s) as Box<dyn LpSolution>)
+        .map_err(|e| Box::new(e) as Box<dyn Error>)
+}
+
+pub fn n_occupied(Present(p): &Present) -> usize {
+    p.iter().flatten().map(|&b| b as usize).sum()
+}
+
+impl Solution for Day12 {
+    type Input = (Vec<Present>, Vec<PackingProblem>);
+    type PartOne = usize;
+    type PartTwo = NoAnswer;
+
+    fn parse_with(input: &str, strictness: Strictness) -> Result<Self::Input, Box<dyn Error>> {
+        let (presents, problems) = parse::run_with(parse_input, input, strictness)?;
+        // pack needs a count for every present
+        if let Some(p) = problems
+            .iter()
+            .find(|p| p.constraints.len() != presents.len())
+        {
+            return Err(format!(
+                "Invalid input: the {}x{} region has {} counts, but there are {} presents",
+                p.width,
+                p.height,
+                p.constraints.len(),
+                presents.len()
+            )
+            .into());
+        }
+        Ok((presents, problems))
+    }
+
+    fn part_one((presents, problems): &Self::Input) -> Result<Self::PartOne, Box<dyn Error>> {
+        // Prune problems where there is not sufficient area even with perfect packing
+        let prune_infeasible = false;
+        // short-circuit when the problem is trivially solvable by putting each present in its
+        // own 3×3 box
+        let short_circuit_trivial = false;
+        Ok(problems
+            .par_iter()
+            .filter(|p| {
+                !prune_infeasible
+                    || p.width * p.height
+                        >= Iterator::zip(p.constraints.iter(), presents.iter().map(n_occupied))
+                            .map(|(c, n)| c * n)
+                            .sum()
+            })
+            .filter(|p| {
+                (short_circuit_trivial
+                    && (p.width / 3) * (p.height / 3) >= p.constraints.iter().sum())
+                    || pack(presents, p).is_ok()
+            })
+            .count())
+    }
+
+    fn part_two(_: &Self::Input) -> Result<Self::PartTwo, Box<dyn Error>> {
+        Ok(NoAnswer)
+    }
+}
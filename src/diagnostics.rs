@@ -0,0 +1,48 @@
+use std::fmt::Display;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use serde_json::{json, Value};
+
+// Days report intermediate results and statistics through here instead of printing them. By
+// default they are printed right away, but when capturing they are collected so that they can be
+// attached to the answer they belong to, e.g. in JSON output.
+
+static CAPTURE: AtomicBool = AtomicBool::new(false);
+static COLLECTED: Mutex<Vec<Diagnostic>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub name: String,
+    pub value: Value,
+}
+
+impl Diagnostic {
+    pub fn to_json(&self) -> Value {
+        json!({ "name": self.name, "value": self.value })
+    }
+}
+
+pub fn capture(enabled: bool) {
+    CAPTURE.store(enabled, Ordering::Relaxed);
+}
+
+// Report a diagnostic, with a value for machine consumption and a message for humans
+pub fn report(name: &str, value: impl Into<Value>, message: impl Display) {
+    if CAPTURE.load(Ordering::Relaxed) {
+        // A poisoned lock only means another thread panicked while pushing, the data is fine
+        let mut collected = COLLECTED.lock().unwrap_or_else(|e| e.into_inner());
+        collected.push(Diagnostic {
+            name: name.to_string(),
+            value: value.into(),
+        });
+    } else {
+        println!("{message}");
+    }
+}
+
+// Take the diagnostics collected since the last call
+pub fn take() -> Vec<Diagnostic> {
+    let mut collected = COLLECTED.lock().unwrap_or_else(|e| e.into_inner());
+    std::mem::take(&mut collected)
+}
@@ -0,0 +1,70 @@
+// A dial numbered 0 to size - 1 that wraps around, like a safe's. Turning it right (a positive
+// number of clicks) counts up, and turning it left (negative) counts down.
+//
+// Each click moves the dial by one, and the clicks that leave it pointing at 0 are what counts:
+// - A rotation that ends on 0 landed on zero, and its last click is one of its clicks on zero.
+// - A rotation that starts on 0 doesn't count that, since the dial was already there. Its first
+//   click moves away from 0 whichever way it turns.
+// - A full turn, size clicks either way, passes 0 exactly once wherever it starts.
+// - A rotation of 0 clicks doesn't click at all, and landed on zero if the dial is at 0.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Dial {
+    size: u64,
+    position: u64,
+}
+
+// What one rotation did
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Turn {
+    // Where the dial points afterwards
+    pub position: u64,
+    pub landed_on_zero: bool,
+    // How many clicks left the dial at 0, including the last one if it landed there
+    pub clicks_on_zero: u64,
+}
+
+impl Dial {
+    // A dial with size positions pointing at start, which wraps around if it's too big. Panics if
+    // size is 0.
+    pub fn new(size: u64, start: u64) -> Dial {
+        assert!(size > 0, "a dial needs at least one position");
+        Dial {
+            size,
+            position: start % size,
+        }
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    pub fn rotate(&mut self, clicks: i64) -> Turn {
+        let (size, start) = (i128::from(self.size), i128::from(self.position));
+        let end = start + i128::from(clicks);
+        // The clicks stop at start + 1, ..., end going right, and at start - 1, ..., end going
+        // left. Count the multiples of size among them.
+        let clicks_on_zero = if clicks >= 0 {
+            end.div_euclid(size) - start.div_euclid(size)
+        } else {
+            (start - 1).div_euclid(size) - (end - 1).div_euclid(size)
+        };
+        self.position = end.rem_euclid(size) as u64;
+        Turn {
+            position: self.position,
+            landed_on_zero: self.position == 0,
+            clicks_on_zero: clicks_on_zero as u64,
+        }
+    }
+
+    // Apply each rotation in turn
+    pub fn rotations(
+        &mut self,
+        rotations: impl IntoIterator<Item = i64>,
+    ) -> impl Iterator<Item = Turn> {
+        rotations.into_iter().map(|clicks| self.rotate(clicks))
+    }
+}
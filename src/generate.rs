@@ -0,0 +1,281 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use itertools::Itertools;
+use rand::rngs::StdRng;
+use rand::seq::{index, SliceRandom};
+use rand::{Rng, SeedableRng};
+
+// Random puzzle inputs in the format each day's parser expects, for stress testing and
+// benchmarking. Each generator takes a seeded RNG, so the same seed and size always give the same
+// input, and a size whose meaning depends on the day (usually the number of lines).
+
+pub type GenerateFn = fn(&mut StdRng, usize) -> String;
+
+pub fn rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+// Write a generated input to path, refusing to overwrite existing examples
+pub fn write(path: &Path, input: &str) -> Result<(), Box<dyn Error>> {
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()).into());
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, input).map_err(|e| format!("Could not write {}: {e}", path.display()).into())
+}
+
+fn lines(lines: impl Iterator<Item = String>) -> String {
+    lines.map(|l| l + "\n").collect()
+}
+
+// A number with exactly d digits
+fn with_digits(rng: &mut StdRng, d: u32) -> u64 {
+    rng.random_range(10u64.pow(d - 1)..10u64.pow(d))
+}
+
+// size rotations
+pub fn day01(rng: &mut StdRng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let dir = if rng.random_bool(0.5) { 'L' } else { 'R' };
+        format!("{dir}{}", rng.random_range(1..1000))
+    }))
+}
+
+// size ID ranges of up to 10 digits, each crossing at most one power of 10
+pub fn day02(rng: &mut StdRng, size: usize) -> String {
+    let mut spans = (0..size).map(|_| {
+        let d = rng.random_range(1..=10);
+        let start = with_digits(rng, d);
+        let end = (start + rng.random_range(0..100_000)).min(10u64.pow(d.min(9) + 1) - 1);
+        format!("{start}-{end}")
+    });
+    spans.join(",") + "\n"
+}
+
+// size banks of 100 batteries
+pub fn day03(rng: &mut StdRng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        (0..100)
+            .map(|_| rng.random_range(1..=9).to_string())
+            .collect()
+    }))
+}
+
+// A size × size grid of paper rolls
+pub fn day04(rng: &mut StdRng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        (0..size)
+            .map(|_| if rng.random_bool(0.6) { '@' } else { '.' })
+            .collect()
+    }))
+}
+
+// size fresh ranges followed by size ingredient IDs
+pub fn day05(rng: &mut StdRng, size: usize) -> String {
+    let max = 1_000_000_000_000u64;
+    let ranges = lines((0..size).map(|_| {
+        let lo = rng.random_range(1..max);
+        let hi = lo + rng.random_range(0..max / 100);
+        format!("{lo}-{hi}")
+    }));
+    let ids = lines((0..size).map(|_| rng.random_range(1..max + max / 100).to_string()));
+    format!("{ranges}\n{ids}")
+}
+
+// A worksheet with size problems of four numbers each
+pub fn day06(rng: &mut StdRng, size: usize) -> String {
+    let rows = 4;
+    let mut lines = vec![Vec::new(); rows + 1];
+    for _ in 0..size {
+        // The numbers in a problem are aligned to the same side, and their lengths are sorted so
+        // that every column of digits is contiguous when read top to bottom
+        let mut lengths = (0..rows).map(|_| rng.random_range(1..=4)).collect_vec();
+        lengths.sort();
+        if rng.random_bool(0.5) {
+            lengths.reverse();
+        }
+        let width = *lengths.iter().max().unwrap_or(&1) as usize;
+        let right = rng.random_bool(0.5);
+        for (line, &d) in lines.iter_mut().zip(&lengths) {
+            let n = with_digits(rng, d);
+            line.push(if right {
+                format!("{n:>width$}")
+            } else {
+                format!("{n:<width$}")
+            });
+        }
+        let op = if rng.random_bool(0.5) { '+' } else { '*' };
+        lines[rows].push(format!("{op:<width$}"));
+    }
+    self::lines(lines.into_iter().map(|l| l.join(" ")))
+}
+
+// A manifold size splitter rows deep and 2 * size + 1 wide
+pub fn day07(rng: &mut StdRng, size: usize) -> String {
+    let width = 2 * size + 1;
+    let empty = ".".repeat(width);
+    let mut out = vec![
+        format!("{}S{}", &empty[..size], &empty[..size]),
+        empty.clone(),
+    ];
+    for _ in 0..size {
+        out.push(
+            (0..width)
+                .map(|_| if rng.random_bool(0.3) { '^' } else { '.' })
+                .collect(),
+        );
+        out.push(empty.clone());
+    }
+    lines(out.into_iter())
+}
+
+// size junction boxes
+pub fn day08(rng: &mut StdRng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let [x, y, z] = [(); 3].map(|_| rng.random_range(0..100_000));
+        format!("{x},{y},{z}")
+    }))
+}
+
+// A rectilinear polygon of size columns with 4 * size red corners. Each column has a bottom below
+// and a top above a common middle line, so neighbouring columns always overlap and the loop never
+// touches itself. The x coordinates spread further when there are too many columns to fit.
+pub fn day09(rng: &mut StdRng, size: usize) -> String {
+    let size = size.max(1);
+    let middle = 50_000;
+    let width = (2 * (size + 1)).max(100_000);
+    let mut xs = index::sample(rng, width, size + 1)
+        .into_iter()
+        .map(|x| x as u64)
+        .collect_vec();
+    xs.sort();
+    // Neighbouring columns must differ in height, or their shared corner wouldn't be a corner
+    let mut heights = |lo: u64, hi: u64| {
+        let mut out: Vec<u64> = Vec::with_capacity(size);
+        while out.len() < size {
+            let h = rng.random_range(lo..hi);
+            if out.last() != Some(&h) {
+                out.push(h);
+            }
+        }
+        out
+    };
+    let bottoms = heights(0, middle);
+    let tops = heights(middle + 1, 2 * middle);
+
+    let mut corners = Vec::with_capacity(4 * size);
+    for i in 0..size {
+        corners.push((xs[i], bottoms[i]));
+        corners.push((xs[i + 1], bottoms[i]));
+    }
+    for i in (0..size).rev() {
+        corners.push((xs[i + 1], tops[i]));
+        corners.push((xs[i], tops[i]));
+    }
+    lines(corners.into_iter().map(|(x, y)| format!("{x},{y}")))
+}
+
+// size machines. The lights and joltage requirements are made by actually pressing the buttons,
+// so every machine can be configured.
+pub fn day10(rng: &mut StdRng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let n = rng.random_range(3..=10);
+        let buttons = (0..rng.random_range(2..=n + 3))
+            .map(|_| {
+                let k = rng.random_range(1..=n);
+                let mut b = index::sample(rng, n, k).into_vec();
+                b.sort();
+                b
+            })
+            .collect_vec();
+        let mut lights = vec![false; n];
+        while !lights.contains(&true) {
+            for b in &buttons {
+                if rng.random_bool(0.5) {
+                    b.iter().for_each(|&l| lights[l] = !lights[l]);
+                }
+            }
+        }
+        let mut joltages = vec![0; n];
+        for b in &buttons {
+            let presses = rng.random_range(0..20);
+            b.iter().for_each(|&l| joltages[l] += presses);
+        }
+
+        let lights: String = lights.iter().map(|&l| if l { '#' } else { '.' }).collect();
+        let buttons = buttons
+            .iter()
+            .map(|b| format!("({})", b.iter().join(",")))
+            .join(" ");
+        format!("[{lights}] {buttons} {{{}}}", joltages.iter().join(","))
+    }))
+}
+
+// Every name of three or more letters, shortest first: aaa, aab, ..., zzz, aaaa, ...
+fn all_names() -> impl Iterator<Item = String> {
+    (3..).flat_map(|len| {
+        (0..26_usize.pow(len)).map(move |k| {
+            (0..len)
+                .rev()
+                .map(|i| char::from(b'a' + (k / 26_usize.pow(i) % 26) as u8))
+                .collect()
+        })
+    })
+}
+
+// A DAG of size devices (besides svr, you, fft, dac and out). Every device only feeds devices
+// later in a random order, with svr first and out last.
+pub fn day11(rng: &mut StdRng, size: usize) -> String {
+    let special = ["svr", "you", "fft", "dac", "out"];
+    let mut names = vec!["you".to_string(), "fft".to_string(), "dac".to_string()];
+    names.extend(
+        all_names()
+            .filter(|name| !special.contains(&name.as_str()))
+            .take(size),
+    );
+    names.shuffle(rng);
+    names.insert(0, "svr".to_string());
+    names.push("out".to_string());
+
+    let n = names.len();
+    lines((0..n - 1).map(|i| {
+        let k = rng.random_range(1..=3.min(n - 1 - i));
+        let outputs = index::sample(rng, n - 1 - i, k)
+            .into_iter()
+            .map(|j| &names[i + 1 + j])
+            .join(" ");
+        format!("{}: {outputs}", names[i])
+    }))
+}
+
+// Six presents and size regions of up to 50 × 50
+pub fn day12(rng: &mut StdRng, size: usize) -> String {
+    let mut out = String::new();
+    for n in 0..6 {
+        let k = rng.random_range(5..=7);
+        let cells = index::sample(rng, 9, k).into_vec();
+        let shape = (0..3)
+            .map(|y| {
+                (0..3)
+                    .map(|x| {
+                        if cells.contains(&(3 * y + x)) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+            })
+            .join("\n");
+        out += &format!("{n}:\n{shape}\n\n");
+    }
+    out + &lines((0..size).map(|_| {
+        let [w, h] = [(); 2].map(|_| rng.random_range(4..=50));
+        let counts = (0..6).map(|_| rng.random_range(0..=(w * h) / 60)).join(" ");
+        format!("{w}x{h}: {counts}")
+    }))
+}
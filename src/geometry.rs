@@ -0,0 +1,446 @@
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Range;
+
+use itertools::Itertools;
+use num::{PrimInt, ToPrimitive};
+
+use crate::grid::{Grid, UPoint};
+use crate::point::{Coordinate, Point2};
+
+// A polygon whose edges are all horizontal or vertical, for asking which lattice points it
+// covers. The edges run between consecutive vertices and from the last back to the first, and a
+// point is covered when it is inside the polygon or on its boundary.
+//
+// Only the coordinates of the vertices matter: every column between two neighboring vertex xs
+// is covered the same way, and so is every row between two vertex ys. So the polygon is kept as
+// a grid of classes, one for each vertex x and one for the xs strictly between two of them (and
+// the same for y), along with a prefix sum of the classes that aren't covered. A query finds its
+// classes by binary search and then counts uncovered classes in constant time. Building it
+// takes O(n^2 log n) time and O(n^2) memory for n vertices.
+#[derive(Clone, Debug)]
+pub struct RectilinearPolygon<T> {
+    vertices: Vec<Point2<T>>,
+    xs: Vec<T>,
+    ys: Vec<T>,
+    // How many classes left of x and above y aren't covered, at (x, y)
+    uncovered: Grid<usize>,
+}
+
+impl<T: PrimInt> RectilinearPolygon<T> {
+    // The polygon through vertices, which must form a loop of horizontal and vertical edges that
+    // doesn't touch itself. Checking that takes O(n^2) time.
+    pub fn new(vertices: Vec<Point2<T>>) -> Result<RectilinearPolygon<T>, PolygonError<T>> {
+        validate(&vertices)?;
+        let xs = vertices
+            .iter()
+            .map(Point2::x)
+            .sorted()
+            .dedup()
+            .collect_vec();
+        let ys = vertices
+            .iter()
+            .map(Point2::y)
+            .sorted()
+            .dedup()
+            .collect_vec();
+        let covered = classify(&vertices, &xs, &ys);
+
+        let mut uncovered = Grid::new(covered.width() + 1, covered.height() + 1, 0);
+        for p in covered.points() {
+            let below = UPoint {
+                x: p.x + 1,
+                y: p.y + 1,
+            };
+            uncovered[below] = uncovered[UPoint { x: p.x, y: p.y + 1 }]
+                + uncovered[UPoint { x: p.x + 1, y: p.y }]
+                - uncovered[p]
+                + usize::from(!covered[p]);
+        }
+        Ok(RectilinearPolygon {
+            vertices,
+            xs,
+            ys,
+            uncovered,
+        })
+    }
+
+    pub fn vertices(&self) -> &[Point2<T>] {
+        &self.vertices
+    }
+
+    // Whether p is inside or on the boundary
+    pub fn contains(&self, p: Point2<T>) -> bool {
+        self.contains_rect(p, p)
+    }
+
+    // Whether every lattice point of the rectangle with opposite corners a and b is covered
+    pub fn contains_rect(&self, a: Point2<T>, b: Point2<T>) -> bool {
+        let span = |a: T, b: T, coordinates: &[T]| {
+            Some(class(coordinates, a.min(b))?..class(coordinates, a.max(b))? + 1)
+        };
+        match (span(a.x(), b.x(), &self.xs), span(a.y(), b.y(), &self.ys)) {
+            (Some(xs), Some(ys)) => self.count_uncovered(xs, ys) == 0,
+            _ => false,
+        }
+    }
+
+    fn count_uncovered(&self, xs: Range<usize>, ys: Range<usize>) -> usize {
+        let at = |x, y| self.uncovered[UPoint { x, y }];
+        at(xs.end, ys.end) + at(xs.start, ys.start) - at(xs.start, ys.end) - at(xs.end, ys.start)
+    }
+}
+
+// Measures for puzzles that count lattice points rather than ask about particular ones
+impl<T: PrimInt + Coordinate> RectilinearPolygon<T> {
+    // The area enclosed by the boundary, or None if the area of the bounding box doesn't fit
+    pub fn area(&self) -> Option<u128> {
+        let (left, top) = (self.xs[0], self.ys[0]);
+        let (right, bottom) = (self.xs[self.xs.len() - 1], self.ys[self.ys.len() - 1]);
+        distance(left, right).checked_mul(distance(top, bottom))?;
+        // The shoelace formula, which for horizontal and vertical edges sums the rectangles
+        // between each horizontal edge and the top, added going left and subtracted going right.
+        // None of those is bigger than the bounding box, so the sum is exact modulo 2^128 and the
+        // area is either it or its negation.
+        let sum = self.edges().fold(0_u128, |sum, (a, b)| {
+            let rectangle = distance(a.x(), b.x()).wrapping_mul(distance(top, a.y()));
+            if a.y() != b.y() {
+                sum
+            } else if a.x() > b.x() {
+                sum.wrapping_add(rectangle)
+            } else {
+                sum.wrapping_sub(rectangle)
+            }
+        });
+        // The sum is positive when the loop goes right from its top left vertex
+        let (i, first) = self
+            .vertices
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, p)| (p.y(), p.x()))
+            .expect("polygons have vertices");
+        let next = self.vertices[(i + 1) % self.vertices.len()];
+        Some(if next.y() == first.y() {
+            sum
+        } else {
+            sum.wrapping_neg()
+        })
+    }
+
+    // The number of lattice points on the boundary, or None if it doesn't fit
+    pub fn boundary_points(&self) -> Option<u128> {
+        self.edges().try_fold(0_u128, |sum, (a, b)| {
+            sum.checked_add(distance(a.x(), b.x()) + distance(a.y(), b.y()))
+        })
+    }
+
+    // The number of lattice points strictly inside, by Pick's theorem: the area is the number of
+    // interior points plus half the boundary points minus one. A closed loop of horizontal and
+    // vertical edges has an even number of boundary points.
+    pub fn interior_points(&self) -> Option<u128> {
+        self.area()?
+            .checked_add(1)?
+            .checked_sub(self.boundary_points()? / 2)
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (&Point2<T>, &Point2<T>)> {
+        self.vertices.iter().circular_tuple_windows()
+    }
+}
+
+// One of the coordinates is the same along an edge, so the other is its length
+fn distance<T: Coordinate>(a: T, b: T) -> u128 {
+    a.abs_diff(b)
+        .to_u128()
+        .expect("unsigned integers fit in a u128")
+}
+
+// The class of a coordinate: 2i for the ith vertex coordinate, and 2i + 1 for those strictly
+// between the ith and the next. None if it's outside all vertices, where nothing is covered.
+fn class<T: PrimInt>(coordinates: &[T], c: T) -> Option<usize> {
+    match coordinates.binary_search(&c) {
+        Ok(i) => Some(2 * i),
+        Err(i) if i > 0 && i < coordinates.len() => Some(2 * i - 1),
+        Err(_) => None,
+    }
+}
+
+// A coordinate of a class, or None if the class is between neighboring coordinates and empty
+fn representative<T: PrimInt>(coordinates: &[T], class: usize) -> Option<T> {
+    let c = coordinates[class / 2];
+    if class.is_multiple_of(2) {
+        Some(c)
+    } else {
+        Some(c + T::one()).filter(|&c| c < coordinates[class / 2 + 1])
+    }
+}
+
+// Which classes are covered. Empty classes have nothing uncovered, so count as covered.
+fn classify<T: PrimInt>(vertices: &[Point2<T>], xs: &[T], ys: &[T]) -> Grid<bool> {
+    let edges = vertices.iter().circular_tuple_windows().collect_vec();
+    let mut covered = Grid::new(2 * xs.len() - 1, 2 * ys.len() - 1, true);
+    for cy in 0..covered.height() {
+        let Some(y) = representative(ys, cy) else {
+            continue;
+        };
+        // A point off the boundary is inside if a ray to its left crosses an odd number of
+        // edges. Counting the vertical edges from their top up to but not including their bottom
+        // counts a vertex on the ray once where the boundary crosses the row there, and zero or
+        // two times where it only touches it.
+        let crossings = edges
+            .iter()
+            .filter(|(a, b)| a.x() == b.x() && a.y().min(b.y()) <= y && y < a.y().max(b.y()))
+            .map(|(a, _)| a.x())
+            .sorted()
+            .collect_vec();
+        // The parts of the row on the boundary, merged
+        let boundary = edges
+            .iter()
+            .filter(|(a, b)| a.y().min(b.y()) <= y && y <= a.y().max(b.y()))
+            .map(|(a, b)| (a.x().min(b.x()), a.x().max(b.x())))
+            .sorted()
+            .coalesce(|(l1, r1), (l2, r2)| {
+                if l2 <= r1 {
+                    Ok((l1, r1.max(r2)))
+                } else {
+                    Err(((l1, r1), (l2, r2)))
+                }
+            })
+            .collect_vec();
+        for cx in 0..covered.width() {
+            let Some(x) = representative(xs, cx) else {
+                continue;
+            };
+            let inside = crossings.partition_point(|&c| c < x) % 2 == 1;
+            let on_boundary = match boundary.partition_point(|&(l, _)| l <= x) {
+                0 => false,
+                i => x <= boundary[i - 1].1,
+            };
+            covered[UPoint { x: cx, y: cy }] = inside || on_boundary;
+        }
+    }
+    covered
+}
+
+// Why some vertices don't make a polygon. The indices are of the vertices that start the edges.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PolygonError<T> {
+    Empty,
+    // A vertex is the same as the one before it
+    Repeated {
+        index: usize,
+        at: Point2<T>,
+    },
+    // The edge from a vertex to the next is neither horizontal nor vertical
+    Diagonal {
+        index: usize,
+        from: Point2<T>,
+        to: Point2<T>,
+    },
+    // The same, for the edge from the last vertex back to the first
+    NotClosed {
+        from: Point2<T>,
+        to: Point2<T>,
+    },
+    // Two edges cross, touch or overlap other than where neighbors meet
+    SelfIntersecting {
+        first: usize,
+        second: usize,
+    },
+}
+
+impl<T: Display> Display for PolygonError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolygonError::Empty => write!(f, "the polygon has no vertices"),
+            PolygonError::Repeated { index, at } => {
+                write!(f, "vertex {index} at {at} is the same as the one before it")
+            }
+            PolygonError::Diagonal { index, from, to } => write!(
+                f,
+                "the edge from vertex {index} at {from} to {to} is neither horizontal nor vertical"
+            ),
+            PolygonError::NotClosed { from, to } => write!(
+                f,
+                "the polygon doesn't close: the last vertex at {from} shares no row or column \
+                 with the first at {to}"
+            ),
+            PolygonError::SelfIntersecting { first, second } => {
+                write!(f, "the edges from vertices {first} and {second} intersect")
+            }
+        }
+    }
+}
+
+impl<T: Debug + Display> std::error::Error for PolygonError<T> {}
+
+fn validate<T: PrimInt>(vertices: &[Point2<T>]) -> Result<(), PolygonError<T>> {
+    let n = vertices.len();
+    if n == 0 {
+        return Err(PolygonError::Empty);
+    }
+    let edges = (0..n)
+        .map(|i| (vertices[i], vertices[(i + 1) % n]))
+        .collect_vec();
+    for (index, &(from, to)) in edges.iter().enumerate() {
+        if from == to {
+            return Err(PolygonError::Repeated {
+                index: (index + 1) % n,
+                at: to,
+            });
+        }
+        if from.x() != to.x() && from.y() != to.y() {
+            return Err(if index == n - 1 {
+                PolygonError::NotClosed { from, to }
+            } else {
+                PolygonError::Diagonal { index, from, to }
+            });
+        }
+    }
+    // Horizontal and vertical segments intersect exactly when their bounding boxes do. Neighbors
+    // always meet at their shared vertex, so for them only turning back along the same line
+    // counts.
+    let intersect = |(a, b): (Point2<T>, Point2<T>), (c, d): (Point2<T>, Point2<T>)| {
+        a.x().min(b.x()).max(c.x().min(d.x())) <= a.x().max(b.x()).min(c.x().max(d.x()))
+            && a.y().min(b.y()).max(c.y().min(d.y())) <= a.y().max(b.y()).min(c.y().max(d.y()))
+    };
+    let turns_back = |(a, b): (Point2<T>, Point2<T>), (_, c): (Point2<T>, Point2<T>)| {
+        let along = |a: T, b: T, c: T| (a < b) == (c < b);
+        (a.y() == c.y() && a.y() == b.y() && along(a.x(), b.x(), c.x()))
+            || (a.x() == c.x() && a.x() == b.x() && along(a.y(), b.y(), c.y()))
+    };
+    for (first, second) in (0..n).tuple_combinations() {
+        let failed = if second == first + 1 {
+            turns_back(edges[first], edges[second])
+        } else if first == 0 && second == n - 1 {
+            turns_back(edges[second], edges[first])
+        } else {
+            intersect(edges[first], edges[second])
+        };
+        if failed {
+            return Err(PolygonError::SelfIntersecting { first, second });
+        }
+    }
+    Ok(())
+}
+
+// The largest rectangle with two of the points as opposite corners, counting the lattice points
+// it covers
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LargestRectangle<T> {
+    pub corners: (Point2<T>, Point2<T>),
+    // Saturating at u128::MAX, which only two corners 2^64 apart in both directions reach
+    pub area: u128,
+}
+
+// Find the largest rectangle in O(n log n), or None with fewer than two points.
+//
+// Take the rectangles whose first corner is above and left of the second; the others are the
+// same with the y axis flipped. Nothing can beat a first corner that has another point above
+// and left of it, so only the points without one are candidates, a staircase going down from
+// left to right. The same goes for second corners, with a staircase of the points with nothing
+// below and right of them. Going right along the first staircase, the best partner only moves
+// right along the second one, so dividing and conquering finds them all while looking at each
+// partner O(log n) times.
+pub fn largest_rectangle<T: Copy + Ord + Into<i128>>(
+    points: &[Point2<T>],
+) -> Option<LargestRectangle<T>> {
+    if points.len() < 2 {
+        return None;
+    }
+    let as_i128 =
+        |flip: i128| move |(i, p): (usize, &Point2<T>)| (p.x().into(), flip * p.y().into(), i);
+    let best = [1, -1]
+        .into_iter()
+        .filter_map(|flip| {
+            let points = points.iter().enumerate().map(as_i128(flip)).collect_vec();
+            let (first, second) = staircases(points);
+            let mut best = None;
+            best_partners(&first, &second, 0..first.len(), 0..second.len(), &mut best);
+            best
+        })
+        .max()?;
+    // The best pair could be one point twice, when every point is the same
+    let (Value::Area(area), i, j) = best else {
+        unreachable!("some pair is a rectangle, if only a point twice")
+    };
+    Some(LargestRectangle {
+        corners: (points[i], points[j]),
+        area,
+    })
+}
+
+// The points that could be the first and the second corner, both by increasing x and decreasing y
+type Corner = (i128, i128, usize);
+
+fn staircases(mut points: Vec<Corner>) -> (Vec<Corner>, Vec<Corner>) {
+    points.sort();
+    let mut first: Vec<Corner> = Vec::new();
+    for &p in &points {
+        if first.last().is_none_or(|q| p.1 < q.1) {
+            first.push(p);
+        }
+    }
+    let mut second: Vec<Corner> = Vec::new();
+    for &p in points.iter().rev() {
+        if second.last().is_none_or(|q| p.1 > q.1) {
+            second.push(p);
+        }
+    }
+    second.reverse();
+    (first, second)
+}
+
+// How good a pair of corners is. Rectangles beat pairs where the second corner is on the wrong
+// side in one direction, the further the better, and those beat pairs that are the wrong way
+// around in both. That keeps the best partner moving right.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
+enum Value {
+    WrongWay,
+    OneSideWrong(std::cmp::Reverse<u128>),
+    Area(u128),
+}
+
+fn value(a: Corner, b: Corner) -> Value {
+    // Counting the lattice points means counting from the first corner to one past the second
+    let (dx, dy) = (b.0 + 1 - a.0, b.1 + 1 - a.1);
+    let product = dx.unsigned_abs().saturating_mul(dy.unsigned_abs());
+    match (dx > 0, dy > 0) {
+        (true, true) => Value::Area(product),
+        (false, false) => Value::WrongWay,
+        _ => Value::OneSideWrong(std::cmp::Reverse(product)),
+    }
+}
+
+fn best_partners(
+    first: &[Corner],
+    second: &[Corner],
+    firsts: Range<usize>,
+    partners: Range<usize>,
+    best: &mut Option<(Value, usize, usize)>,
+) {
+    if firsts.is_empty() {
+        return;
+    }
+    let mid = (firsts.start + firsts.end) / 2;
+    let a = first[mid];
+    let (partner, v) = partners
+        .clone()
+        .map(|j| (j, value(a, second[j])))
+        .max_by_key(|&(j, v)| (v, std::cmp::Reverse(j)))
+        .expect("there is always a partner");
+    *best = (*best).max(Some((v, a.2, second[partner].2)));
+    best_partners(
+        first,
+        second,
+        firsts.start..mid,
+        partners.start..partner + 1,
+        best,
+    );
+    best_partners(
+        first,
+        second,
+        mid + 1..firsts.end,
+        partner..partners.end,
+        best,
+    );
+}
@@ -1,9 +1,34 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
 use coordinates::two_dimensional::Vector2;
 use num::traits::{CheckedAdd, CheckedSub};
 
+use crate::parse::Report;
+
+mod direction;
+mod region;
+mod sparse;
+mod transform;
+
+pub use direction::{parse_direction, Direction, StepError};
+pub use region::{Components, Connectivity, Region};
+pub use sparse::SparseGrid;
+pub use transform::{PointSet, Symmetry, Transformable};
+
+// Points on a grid. x is the column, counting from 0 at the left, and y is the row, counting from 0
+// at the top, so North is towards smaller y. A grid read from text has its first line at y = 0.
 pub type UPoint = Vector2<usize>;
 pub type IPoint = Vector2<isize>;
 
+// Grid sizes are below isize::MAX, so their points always fit
+fn to_ipoint(p: UPoint) -> IPoint {
+    IPoint {
+        x: p.x as isize,
+        y: p.y as isize,
+    }
+}
+
 #[derive(Hash, Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
 pub enum CardinalDirection {
     North,
@@ -51,50 +76,35 @@ impl std::ops::Neg for CardinalDirection {
     }
 }
 
-pub fn neighbors_within_bounds(p: &UPoint, (w, h): (usize, usize)) -> Vec<UPoint> {
-    neighbors_unbounded(&p)
-        .iter()
-        .cloned()
-        .filter(|q| q.x <= w && q.y <= h)
-        .collect()
+// The horizontal and vertical neighbors of p that don't underflow
+pub fn neighbors_unbounded(p: &UPoint) -> impl Iterator<Item = UPoint> + use<> {
+    offsets(*p, &ORTHOGONAL)
 }
 
-pub fn neighbors_unbounded(p: &UPoint) -> Vec<UPoint> {
-    vec![
-        p.checked_add(&(1, 0).into()),
-        p.checked_sub(&(1, 0).into()),
-        p.checked_add(&(0, 1).into()),
-        p.checked_sub(&(0, 1).into()),
-    ]
-    .iter()
-    .filter_map(|q| *q)
-    .collect()
+// The horizontal, vertical and diagonal neighbors of p that don't underflow
+pub fn diagonal_neighbors_unbounded(p: &UPoint) -> impl Iterator<Item = UPoint> + use<> {
+    offsets(*p, &ALL_AROUND)
 }
 
-pub fn diagonal_neighbors_within_bounds(p: &UPoint, (w, h): (usize, usize)) -> Vec<UPoint> {
-    diagonal_neighbors_unbounded(&p)
-        .iter()
-        .cloned()
-        .filter(|q| q.x <= w && q.y <= h)
-        .collect()
-}
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const ALL_AROUND: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
 
-pub fn diagonal_neighbors_unbounded(p: &UPoint) -> Vec<UPoint> {
-    vec![
-        p.checked_add(&(1, 0).into()),
-        p.checked_sub(&(1, 0).into()),
-        p.checked_add(&(0, 1).into()),
-        p.checked_sub(&(0, 1).into()),
-        p.checked_add(&(1, 1).into()),
-        p.checked_sub(&(1, 1).into()),
-        p.checked_add(&(0, 1).into())
-            .and_then(|x| x.checked_sub(&(1, 0).into())),
-        p.checked_add(&(1, 0).into())
-            .and_then(|x| x.checked_sub(&(0, 1).into())),
-    ]
-    .iter()
-    .filter_map(|q| *q)
-    .collect()
+fn offsets(p: UPoint, offsets: &'static [(isize, isize)]) -> impl Iterator<Item = UPoint> + use<> {
+    offsets.iter().filter_map(move |&(dx, dy)| {
+        Some(UPoint {
+            x: p.x.checked_add_signed(dx)?,
+            y: p.y.checked_add_signed(dy)?,
+        })
+    })
 }
 
 pub fn neighbors(p: &IPoint) -> Vec<IPoint> {
@@ -105,3 +115,260 @@ pub fn neighbors(p: &IPoint) -> Vec<IPoint> {
         *p + (0isize, -1isize).into(),
     ]
 }
+
+// An axis-aligned rectangle of points with min.x <= x < max.x and min.y <= y < max.y. The
+// extents are exclusive, so a width × height grid is Rect::new(width, height).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Rect<T: num::Num = usize> {
+    pub min: Vector2<T>,
+    pub max: Vector2<T>,
+}
+
+impl<T: num::Num + Copy + PartialOrd> Rect<T> {
+    pub fn from_corners(min: Vector2<T>, max: Vector2<T>) -> Rect<T> {
+        Rect { min, max }
+    }
+
+    pub fn width(&self) -> T {
+        if self.max.x > self.min.x {
+            self.max.x - self.min.x
+        } else {
+            T::zero()
+        }
+    }
+
+    pub fn height(&self) -> T {
+        if self.max.y > self.min.y {
+            self.max.y - self.min.y
+        } else {
+            T::zero()
+        }
+    }
+
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width() == T::zero() || self.height() == T::zero()
+    }
+
+    pub fn contains(&self, p: Vector2<T>) -> bool {
+        self.min.x <= p.x && p.x < self.max.x && self.min.y <= p.y && p.y < self.max.y
+    }
+}
+
+impl Rect<usize> {
+    // The rectangle of a width × height grid
+    pub fn new(width: usize, height: usize) -> Rect {
+        Rect {
+            min: UPoint { x: 0, y: 0 },
+            max: UPoint {
+                x: width,
+                y: height,
+            },
+        }
+    }
+
+    // Every point, row by row
+    pub fn points(&self) -> impl Iterator<Item = UPoint> + use<> {
+        let Rect { min, max } = *self;
+        (min.y..max.y).flat_map(move |y| (min.x..max.x).map(move |x| UPoint { x, y }))
+    }
+
+    // The horizontal and vertical neighbors of p that are inside
+    pub fn neighbors(&self, p: UPoint) -> impl Iterator<Item = UPoint> + use<> {
+        let rect = *self;
+        offsets(p, &ORTHOGONAL).filter(move |&q| rect.contains(q))
+    }
+
+    // The horizontal, vertical and diagonal neighbors of p that are inside
+    pub fn diagonal_neighbors(&self, p: UPoint) -> impl Iterator<Item = UPoint> + use<> {
+        let rect = *self;
+        offsets(p, &ALL_AROUND).filter(move |&q| rect.contains(q))
+    }
+
+    // The points inside that are at most r steps away from p in each direction (the square of
+    // side 2r + 1 around p), except p itself
+    pub fn neighbors_within(&self, p: UPoint, r: usize) -> impl Iterator<Item = UPoint> + use<> {
+        let xs = p.x.saturating_sub(r).max(self.min.x)
+            ..p.x.saturating_add(r).saturating_add(1).min(self.max.x);
+        let ys = p.y.saturating_sub(r).max(self.min.y)
+            ..p.y.saturating_add(r).saturating_add(1).min(self.max.y);
+        ys.flat_map(move |y| xs.clone().map(move |x| UPoint { x, y }))
+            .filter(move |&q| q != p)
+    }
+}
+
+// A rectangular grid of cells, stored row by row and indexed by UPoint { x: column, y: row }
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(UPoint) -> T) -> Grid<T> {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| UPoint { x, y }))
+            .map(&mut f)
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    // Read a grid with one line per row and one char per cell, which cell turns into a T. Every
+    // line must be as long as the first, and blank lines at the end are ignored.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> Option<T>) -> Result<Grid<T>, Report> {
+        let lines: Vec<&str> = input.trim_end_matches(['\r', '\n']).lines().collect();
+        let width = lines.first().map_or(0, |l| l.chars().count());
+        if width == 0 {
+            return Err(Report::new(input, input, "expected a grid".to_string()));
+        }
+
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for line in &lines {
+            let mut n = 0;
+            for (i, c) in line.char_indices() {
+                if n == width {
+                    let expected = format!("expected the row to end after {width} cells");
+                    return Err(Report::new(input, &line[i..], expected));
+                }
+                let value = cell(c)
+                    .ok_or_else(|| Report::new(input, &line[i..], format!("unexpected {c:?}")))?;
+                cells.push(value);
+                n += 1;
+            }
+            if n < width {
+                let expected = format!("expected {width} cells like the first row");
+                return Err(Report::new(input, &line[line.len()..], expected));
+            }
+        }
+        Ok(Grid {
+            width,
+            height: lines.len(),
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn bounds(&self) -> Rect {
+        Rect::new(self.width, self.height)
+    }
+
+    pub fn contains(&self, p: UPoint) -> bool {
+        self.bounds().contains(p)
+    }
+
+    pub fn get(&self, p: UPoint) -> Option<&T> {
+        self.contains(p)
+            .then(|| &self.cells[p.y * self.width + p.x])
+    }
+
+    pub fn get_mut(&mut self, p: UPoint) -> Option<&mut T> {
+        self.contains(p)
+            .then(|| &mut self.cells[p.y * self.width + p.x])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on 0, but then there are no cells anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside the grid");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    // Every point in the grid, row by row
+    pub fn points(&self) -> impl Iterator<Item = UPoint> + use<T> {
+        self.bounds().points()
+    }
+
+    // Every cell along with its point, row by row
+    pub fn cells(&self) -> impl Iterator<Item = (UPoint, &T)> {
+        self.points().zip(&self.cells)
+    }
+
+    pub fn cells_mut(&mut self) -> impl Iterator<Item = (UPoint, &mut T)> {
+        self.points().zip(&mut self.cells)
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(&mut f).collect(),
+        }
+    }
+
+    // The horizontal and vertical neighbors of p that are in the grid
+    pub fn neighbors(&self, p: UPoint) -> impl Iterator<Item = UPoint> + use<T> {
+        self.bounds().neighbors(p)
+    }
+
+    // The horizontal, vertical and diagonal neighbors of p that are in the grid
+    pub fn diagonal_neighbors(&self, p: UPoint) -> impl Iterator<Item = UPoint> + use<T> {
+        self.bounds().diagonal_neighbors(p)
+    }
+}
+
+impl<T> Index<UPoint> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: UPoint) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{p} is outside the {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<UPoint> for Grid<T> {
+    fn index_mut(&mut self, p: UPoint) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p} is outside the {width}x{height} grid"))
+    }
+}
+
+// One line per row, with each cell written by its own Display
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
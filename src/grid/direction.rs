@@ -0,0 +1,252 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use nom::{character::complete::one_of, combinator::map_opt, Parser};
+
+use super::{CardinalDirection, IPoint, Rect, UPoint};
+use crate::parse::IResult;
+
+// One of the eight directions on a grid, in clockwise order from North. North is up, towards
+// smaller y.
+#[derive(Hash, Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+// Why a step left the grid
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum StepError {
+    // A coordinate would have gone below 0
+    Underflow,
+    // The point would have been outside the bounds
+    OutOfBounds,
+}
+
+impl Display for StepError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StepError::Underflow => write!(f, "stepped below 0"),
+            StepError::OutOfBounds => write!(f, "stepped out of bounds"),
+        }
+    }
+}
+
+impl std::error::Error for StepError {}
+
+impl Direction {
+    // Clockwise from North
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub const DIAGONAL: [Direction; 4] = [
+        Direction::NorthEast,
+        Direction::SouthEast,
+        Direction::SouthWest,
+        Direction::NorthWest,
+    ];
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    // Rotate clockwise by eighths × 45°, or counterclockwise if eighths is negative
+    pub fn rotate(self, eighths: i32) -> Direction {
+        Direction::ALL[(self as i32 + eighths).rem_euclid(8) as usize]
+    }
+
+    // Turn 90° to the right
+    pub fn right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    // Turn 90° to the left
+    pub fn left(self) -> Direction {
+        self.rotate(-2)
+    }
+
+    pub fn back(self) -> Direction {
+        self.rotate(4)
+    }
+
+    // The change in position from one step in this direction
+    pub fn delta(self) -> IPoint {
+        let (x, y) = match self {
+            Direction::North => (0, -1),
+            Direction::NorthEast => (1, -1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, -1),
+        };
+        IPoint { x, y }
+    }
+
+    // The direction whose delta is d, if there is one
+    pub fn from_delta(d: IPoint) -> Option<Direction> {
+        Direction::ALL.into_iter().find(|dir| dir.delta() == d)
+    }
+
+    // The direction of a single step from p to q, if they are neighbors
+    pub fn between(p: UPoint, q: UPoint) -> Option<Direction> {
+        let d = |a: usize, b: usize| isize::try_from(b).ok()?.checked_sub_unsigned(a);
+        Direction::from_delta(IPoint {
+            x: d(p.x, q.x)?,
+            y: d(p.y, q.y)?,
+        })
+    }
+
+    // One step from p, or None if a coordinate would underflow
+    pub fn step(self, p: UPoint) -> Option<UPoint> {
+        self.offset(p, 1).ok()
+    }
+
+    // n steps from p, which must stay within bounds
+    pub fn step_n(self, p: UPoint, n: usize, bounds: &Rect) -> Result<UPoint, StepError> {
+        let q = self.offset(p, n)?;
+        if bounds.contains(q) {
+            Ok(q)
+        } else {
+            Err(StepError::OutOfBounds)
+        }
+    }
+
+    // n steps from p without any bounds, except that usize can't go below 0 (underflow) or past
+    // usize::MAX (which is out of any bounds)
+    fn offset(self, p: UPoint, n: usize) -> Result<UPoint, StepError> {
+        let coordinate = |c: usize, d: isize| match d {
+            ..0 => c.checked_sub(n).ok_or(StepError::Underflow),
+            0 => Ok(c),
+            _ => c.checked_add(n).ok_or(StepError::OutOfBounds),
+        };
+        let delta = self.delta();
+        Ok(UPoint {
+            x: coordinate(p.x, delta.x)?,
+            y: coordinate(p.y, delta.y)?,
+        })
+    }
+}
+
+impl std::ops::Neg for Direction {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.back()
+    }
+}
+
+impl From<CardinalDirection> for Direction {
+    fn from(c: CardinalDirection) -> Direction {
+        match c {
+            CardinalDirection::North => Direction::North,
+            CardinalDirection::East => Direction::East,
+            CardinalDirection::South => Direction::South,
+            CardinalDirection::West => Direction::West,
+        }
+    }
+}
+
+impl TryFrom<Direction> for CardinalDirection {
+    type Error = String;
+    fn try_from(d: Direction) -> Result<CardinalDirection, String> {
+        match d {
+            Direction::North => Ok(CardinalDirection::North),
+            Direction::East => Ok(CardinalDirection::East),
+            Direction::South => Ok(CardinalDirection::South),
+            Direction::West => Ok(CardinalDirection::West),
+            _ => Err(format!("{d} is not a cardinal direction")),
+        }
+    }
+}
+
+impl From<Direction> for IPoint {
+    fn from(d: Direction) -> IPoint {
+        d.delta()
+    }
+}
+
+impl TryFrom<IPoint> for Direction {
+    type Error = String;
+    fn try_from(d: IPoint) -> Result<Direction, String> {
+        Direction::from_delta(d).ok_or_else(|| format!("{d} is not a single step"))
+    }
+}
+
+// Arrows (^>v<), compass points (NESW) and up/down/left/right (UDLR) all name the cardinal
+// directions
+impl TryFrom<char> for Direction {
+    type Error = String;
+    fn try_from(c: char) -> Result<Direction, String> {
+        match c {
+            '^' | 'N' | 'U' => Ok(Direction::North),
+            '>' | 'E' | 'R' => Ok(Direction::East),
+            'v' | 'S' | 'D' => Ok(Direction::South),
+            '<' | 'W' | 'L' => Ok(Direction::West),
+            _ => Err(format!("No such direction: {c:?}")),
+        }
+    }
+}
+
+// One of the chars above, or a compass point like NE for the diagonals
+impl FromStr for Direction {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NE" => Ok(Direction::NorthEast),
+            "SE" => Ok(Direction::SouthEast),
+            "SW" => Ok(Direction::SouthWest),
+            "NW" => Ok(Direction::NorthWest),
+            _ => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Direction::try_from(c),
+                    _ => Err(format!("No such direction: {s}")),
+                }
+            }
+        }
+    }
+}
+
+// The compass point, as accepted by FromStr
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Direction::North => "N",
+            Direction::NorthEast => "NE",
+            Direction::East => "E",
+            Direction::SouthEast => "SE",
+            Direction::South => "S",
+            Direction::SouthWest => "SW",
+            Direction::West => "W",
+            Direction::NorthWest => "NW",
+        };
+        f.pad(name)
+    }
+}
+
+// A single char naming a cardinal direction, for parsing e.g. a line of moves like ^^>v<
+pub fn parse_direction(input: &str) -> IResult<&str, Direction> {
+    map_opt(one_of("^>v<NESWUDLR"), |c| Direction::try_from(c).ok()).parse(input)
+}
@@ -0,0 +1,188 @@
+use std::collections::{HashSet, VecDeque};
+
+use itertools::Either;
+
+use super::{Direction, Grid, Rect, UPoint};
+
+// Connected regions of a grid. Two cells are connected if they are neighbors (horizontally and
+// vertically, or also diagonally) and both satisfy the predicate, or for partition, if they are
+// neighbors and hold equal values.
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+impl Connectivity {
+    fn neighbors(self, bounds: &Rect, p: UPoint) -> impl Iterator<Item = UPoint> + use<> {
+        match self {
+            Connectivity::Four => Either::Left(bounds.neighbors(p)),
+            Connectivity::Eight => Either::Right(bounds.diagonal_neighbors(p)),
+        }
+    }
+}
+
+// A set of points, with the measures that fences and gardens tend to ask for
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Region {
+    pub points: HashSet<UPoint>,
+}
+
+impl Region {
+    pub fn contains(&self, p: UPoint) -> bool {
+        self.points.contains(&p)
+    }
+
+    pub fn area(&self) -> usize {
+        self.points.len()
+    }
+
+    // The number of cell edges between the region and the outside
+    pub fn perimeter(&self) -> usize {
+        self.points
+            .iter()
+            .map(|&p| {
+                Direction::CARDINAL
+                    .into_iter()
+                    .filter(|d| !self.is_inside(d.step(p)))
+                    .count()
+            })
+            .sum()
+    }
+
+    // The number of straight sides of the boundary, counting the sides of holes too. Every side
+    // starts and ends at a corner, so this counts the corners: for each cell and each of its
+    // diagonals, the two cells beside it are both outside (a convex corner), or both inside with
+    // the diagonal outside (a concave corner).
+    pub fn sides(&self) -> usize {
+        let corners = |p: UPoint| {
+            Direction::CARDINAL
+                .into_iter()
+                .filter(|&d| {
+                    let a = self.is_inside(d.step(p));
+                    let b = self.is_inside(d.right().step(p));
+                    let diagonal = self.is_inside(d.rotate(1).step(p));
+                    (!a && !b) || (a && b && !diagonal)
+                })
+                .count()
+        };
+        self.points.iter().map(|&p| corners(p)).sum()
+    }
+
+    // The smallest rectangle containing the region, which is empty for an empty region
+    pub fn bounding_box(&self) -> Rect {
+        let Some(&first) = self.points.iter().next() else {
+            return Rect::new(0, 0);
+        };
+        let (mut min, mut max) = (first, first);
+        for p in &self.points {
+            min = UPoint {
+                x: min.x.min(p.x),
+                y: min.y.min(p.y),
+            };
+            max = UPoint {
+                x: max.x.max(p.x),
+                y: max.y.max(p.y),
+            };
+        }
+        Rect::from_corners(
+            min,
+            UPoint {
+                x: max.x + 1,
+                y: max.y + 1,
+            },
+        )
+    }
+
+    fn is_inside(&self, p: Option<UPoint>) -> bool {
+        p.is_some_and(|p| self.contains(p))
+    }
+}
+
+// The regions of a grid, and which region each cell belongs to
+#[derive(Debug, Clone)]
+pub struct Components {
+    pub labels: Grid<Option<usize>>,
+    pub regions: Vec<Region>,
+}
+
+impl<T> Grid<T> {
+    // The region of cells connected to start that satisfy include, which is empty if start doesn't
+    pub fn flood_fill(
+        &self,
+        start: UPoint,
+        connectivity: Connectivity,
+        include: impl Fn(&T) -> bool,
+    ) -> Region {
+        self.fill(start, connectivity, |_, q| include(&self[q]))
+    }
+
+    // Every region of connected cells that satisfy include, labelled in the order of their first
+    // cell row by row
+    pub fn components(
+        &self,
+        connectivity: Connectivity,
+        include: impl Fn(&T) -> bool,
+    ) -> Components {
+        self.label(
+            connectivity,
+            |p| include(&self[p]),
+            |_, q| include(&self[q]),
+        )
+    }
+
+    // Split the whole grid into regions of equal, connected cells
+    pub fn partition(&self, connectivity: Connectivity) -> Components
+    where
+        T: PartialEq,
+    {
+        self.label(connectivity, |_| true, |p, q| self[p] == self[q])
+    }
+
+    fn label(
+        &self,
+        connectivity: Connectivity,
+        include: impl Fn(UPoint) -> bool,
+        joins: impl Fn(UPoint, UPoint) -> bool,
+    ) -> Components {
+        let mut labels = Grid::new(self.width(), self.height(), None);
+        let mut regions: Vec<Region> = Vec::new();
+        for p in self.points() {
+            if labels[p].is_some() || !include(p) {
+                continue;
+            }
+            let region = self.fill(p, connectivity, &joins);
+            for &q in &region.points {
+                labels[q] = Some(regions.len());
+            }
+            regions.push(region);
+        }
+        Components { labels, regions }
+    }
+
+    // The points reachable from start by steps from p to q with joins(p, q)
+    fn fill(
+        &self,
+        start: UPoint,
+        connectivity: Connectivity,
+        joins: impl Fn(UPoint, UPoint) -> bool,
+    ) -> Region {
+        let mut points = HashSet::new();
+        if !self.contains(start) || !joins(start, start) {
+            return Region { points };
+        }
+        let bounds = self.bounds();
+        let mut queue = VecDeque::from([start]);
+        points.insert(start);
+        while let Some(p) = queue.pop_front() {
+            for q in connectivity.neighbors(&bounds, p) {
+                if !points.contains(&q) && joins(p, q) {
+                    points.insert(q);
+                    queue.push_back(q);
+                }
+            }
+        }
+        Region { points }
+    }
+}
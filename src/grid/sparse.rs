@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::ops::Index;
+
+use super::{to_ipoint, Grid, IPoint, Rect, UPoint};
+
+// A grid without edges that only stores the cells that were set, for worlds that are infinite or
+// mostly empty. It keeps track of the smallest rectangle holding every cell.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<IPoint, T>,
+    bounds: Option<Rect<isize>>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    // Every cell of a dense grid that keep accepts, with the top left corner at the origin
+    pub fn from_grid(grid: Grid<T>, mut keep: impl FnMut(&T) -> bool) -> SparseGrid<T> {
+        let width = grid.width;
+        grid.cells
+            .into_iter()
+            .enumerate()
+            .filter(|(_, cell)| keep(cell))
+            .map(|(i, cell)| {
+                (
+                    to_ipoint(UPoint {
+                        x: i % width,
+                        y: i / width,
+                    }),
+                    cell,
+                )
+            })
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // The smallest rectangle holding every cell, which is empty if there are none
+    pub fn bounds(&self) -> Rect<isize> {
+        self.bounds.unwrap_or(Rect::from_corners(
+            IPoint { x: 0, y: 0 },
+            IPoint { x: 0, y: 0 },
+        ))
+    }
+
+    pub fn contains(&self, p: IPoint) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn get(&self, p: IPoint) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: IPoint) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    // Set a cell, returning what was there before
+    pub fn insert(&mut self, p: IPoint, value: T) -> Option<T> {
+        self.bounds = Some(grow(self.bounds, p));
+        self.cells.insert(p, value)
+    }
+
+    // Clear a cell. The bounds only need recomputing when it was on their edge.
+    pub fn remove(&mut self, p: IPoint) -> Option<T> {
+        let removed = self.cells.remove(&p)?;
+        let on_edge = |b: Rect<isize>| {
+            p.x == b.min.x || p.y == b.min.y || p.x + 1 == b.max.x || p.y + 1 == b.max.y
+        };
+        if self.bounds.is_some_and(on_edge) {
+            self.bounds = self.cells.keys().fold(None, |b, &q| Some(grow(b, q)));
+        }
+        Some(removed)
+    }
+
+    // The cells in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (IPoint, &T)> {
+        self.cells.iter().map(|(&p, cell)| (p, cell))
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = IPoint> + '_ {
+        self.cells.keys().copied()
+    }
+
+    // A dense copy of the viewport, with its top left corner at (0, 0). Each cell is made by f
+    // from the cell at the same place, if there is one.
+    pub fn to_grid<U>(&self, viewport: Rect<isize>, mut f: impl FnMut(Option<&T>) -> U) -> Grid<U> {
+        let (width, height) = (viewport.width() as usize, viewport.height() as usize);
+        Grid::from_fn(width, height, |p| f(self.get(viewport.min + to_ipoint(p))))
+    }
+
+    // The viewport as text, one line per row, with a character for each cell made by f
+    pub fn render(&self, viewport: Rect<isize>, f: impl FnMut(Option<&T>) -> char) -> String {
+        self.to_grid(viewport, f).to_string()
+    }
+}
+
+// The smallest rectangle holding both bounds and p
+fn grow(bounds: Option<Rect<isize>>, p: IPoint) -> Rect<isize> {
+    let end = p + IPoint { x: 1, y: 1 };
+    let Some(Rect { min, max }) = bounds else {
+        return Rect::from_corners(p, end);
+    };
+    Rect::from_corners(
+        IPoint {
+            x: min.x.min(p.x),
+            y: min.y.min(p.y),
+        },
+        IPoint {
+            x: max.x.max(end.x),
+            y: max.y.max(end.y),
+        },
+    )
+}
+
+impl<T> From<Grid<T>> for SparseGrid<T> {
+    fn from(grid: Grid<T>) -> SparseGrid<T> {
+        SparseGrid::from_grid(grid, |_| true)
+    }
+}
+
+impl<T> FromIterator<(IPoint, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (IPoint, T)>>(iter: I) -> SparseGrid<T> {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<(IPoint, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (IPoint, T)>>(&mut self, iter: I) {
+        for (p, cell) in iter {
+            self.insert(p, cell);
+        }
+    }
+}
+
+impl<T> Index<IPoint> for SparseGrid<T> {
+    type Output = T;
+
+    fn index(&self, p: IPoint) -> &T {
+        self.get(p).unwrap_or_else(|| panic!("{p} is not set"))
+    }
+}
+
+// The bounds, one line per row, with '.' for the cells that aren't set
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let Rect { min, max } = self.bounds();
+        for y in min.y..max.y {
+            for x in min.x..max.x {
+                match self.get(IPoint { x, y }) {
+                    Some(cell) => write!(f, "{cell}")?,
+                    None => write!(f, ".")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+use std::hash::Hash;
+
+use itertools::Itertools;
+
+use super::{to_ipoint, Grid, IPoint, UPoint};
+
+// The 8 symmetries of a square (the dihedral group D4). Rotations are clockwise on screen, where y
+// grows downwards.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub enum Symmetry {
+    Identity,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    // Mirror the columns, so the first column becomes the last
+    FlipLeftRight,
+    // Mirror the rows, so the first row becomes the last
+    FlipUpDown,
+    // Swap rows and columns, mirroring along the diagonal from the top left
+    Transpose,
+    // Mirror along the diagonal from the top right
+    AntiTranspose,
+}
+
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity,
+        Symmetry::Rotate90,
+        Symmetry::Rotate180,
+        Symmetry::Rotate270,
+        Symmetry::FlipLeftRight,
+        Symmetry::FlipUpDown,
+        Symmetry::Transpose,
+        Symmetry::AntiTranspose,
+    ];
+
+    // The image of p, turning or mirroring around the origin
+    pub fn apply(self, IPoint { x, y }: IPoint) -> IPoint {
+        let (x, y) = match self {
+            Symmetry::Identity => (x, y),
+            Symmetry::Rotate90 => (-y, x),
+            Symmetry::Rotate180 => (-x, -y),
+            Symmetry::Rotate270 => (y, -x),
+            Symmetry::FlipLeftRight => (-x, y),
+            Symmetry::FlipUpDown => (x, -y),
+            Symmetry::Transpose => (y, x),
+            Symmetry::AntiTranspose => (-y, -x),
+        };
+        IPoint { x, y }
+    }
+
+    pub fn inverse(self) -> Symmetry {
+        match self {
+            Symmetry::Rotate90 => Symmetry::Rotate270,
+            Symmetry::Rotate270 => Symmetry::Rotate90,
+            s => s,
+        }
+    }
+
+    // The symmetry that applies self and then other. Each symmetry is decided by where it sends a
+    // point with distinct, nonzero coordinates.
+    pub fn then(self, other: Symmetry) -> Symmetry {
+        let p = IPoint { x: 1, y: 2 };
+        let image = other.apply(self.apply(p));
+        Symmetry::ALL
+            .into_iter()
+            .find(|s| s.apply(p) == image)
+            .expect("D4 is closed under composition")
+    }
+
+    // Whether width and height trade places
+    pub fn swaps_axes(self) -> bool {
+        matches!(
+            self,
+            Symmetry::Rotate90
+                | Symmetry::Rotate270
+                | Symmetry::Transpose
+                | Symmetry::AntiTranspose
+        )
+    }
+
+    // Where p in a width × height rectangle ends up in the transformed rectangle
+    pub fn apply_within(self, p: UPoint, width: usize, height: usize) -> UPoint {
+        let image = self.apply(to_ipoint(p));
+        let far = self.apply(to_ipoint(UPoint {
+            x: width - 1,
+            y: height - 1,
+        }));
+        UPoint {
+            x: (image.x - far.x.min(0)) as usize,
+            y: (image.y - far.y.min(0)) as usize,
+        }
+    }
+}
+
+// Anything the symmetries of a square act on
+pub trait Transformable: Sized {
+    fn transform(&self, symmetry: Symmetry) -> Self;
+
+    // The distinct images under all 8 symmetries, so orbit().len() counts the orientations
+    fn orbit(&self) -> Vec<Self>
+    where
+        Self: Eq + Hash + Clone,
+    {
+        Symmetry::ALL
+            .into_iter()
+            .map(|s| self.transform(s))
+            .unique()
+            .collect()
+    }
+
+    // The smallest image, which is the same for everything in an orbit
+    fn canonical(&self) -> Self
+    where
+        Self: Ord,
+    {
+        Symmetry::ALL
+            .into_iter()
+            .map(|s| self.transform(s))
+            .min()
+            .expect("there are 8 symmetries")
+    }
+}
+
+impl<T: Clone> Transformable for Grid<T> {
+    fn transform(&self, symmetry: Symmetry) -> Grid<T> {
+        let (width, height) = if symmetry.swaps_axes() {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        };
+        let inverse = symmetry.inverse();
+        Grid::from_fn(width, height, |p| {
+            self[inverse.apply_within(p, width, height)].clone()
+        })
+    }
+}
+
+// Square matrices, indexed by [row][column]
+impl<T: Copy, const N: usize> Transformable for [[T; N]; N] {
+    fn transform(&self, symmetry: Symmetry) -> [[T; N]; N] {
+        let inverse = symmetry.inverse();
+        std::array::from_fn(|y| {
+            std::array::from_fn(|x| {
+                let p = inverse.apply_within(UPoint { x, y }, N, N);
+                self[p.y][p.x]
+            })
+        })
+    }
+}
+
+// Blocks of text, with ragged lines padded with spaces to the longest. The result has a newline
+// after every line.
+impl Transformable for String {
+    fn transform(&self, symmetry: Symmetry) -> String {
+        let lines = self.lines().map(|l| l.chars().collect_vec()).collect_vec();
+        let width = lines.iter().map(Vec::len).max().unwrap_or(0);
+        let block = Grid::from_fn(width, lines.len(), |p| {
+            lines[p.y].get(p.x).copied().unwrap_or(' ')
+        });
+        block.transform(symmetry).to_string()
+    }
+}
+
+// A set of points up to translation: it is moved so that its smallest x and y are 0, and kept
+// sorted row by row
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PointSet(Vec<IPoint>);
+
+impl PointSet {
+    pub fn new(points: impl IntoIterator<Item = IPoint>) -> PointSet {
+        let points = points.into_iter().collect_vec();
+        let min = IPoint {
+            x: points.iter().map(|p| p.x).min().unwrap_or(0),
+            y: points.iter().map(|p| p.y).min().unwrap_or(0),
+        };
+        let mut points = points.into_iter().map(|p| p - min).collect_vec();
+        points.sort_by_key(|p| (p.y, p.x));
+        points.dedup();
+        PointSet(points)
+    }
+
+    pub fn points(&self) -> &[IPoint] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Transformable for PointSet {
+    fn transform(&self, symmetry: Symmetry) -> PointSet {
+        PointSet::new(self.0.iter().map(|&p| symmetry.apply(p)))
+    }
+}
+
+// Row by row, like the points themselves
+impl Ord for PointSet {
+    fn cmp(&self, other: &PointSet) -> std::cmp::Ordering {
+        let key = |s: &PointSet| s.0.iter().map(|p| (p.y, p.x)).collect_vec();
+        key(self).cmp(&key(other))
+    }
+}
+
+impl PartialOrd for PointSet {
+    fn partial_cmp(&self, other: &PointSet) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
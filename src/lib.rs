@@ -1 +1,13 @@
+pub mod answers;
+pub mod bench;
+pub mod days;
+pub mod diagnostics;
+pub mod dial;
+pub mod generate;
+pub mod geometry;
 pub mod grid;
+pub mod parse;
+pub mod point;
+pub mod polyfill;
+pub mod search;
+pub mod solution;
@@ -0,0 +1,213 @@
+use std::fmt::{Debug, Display, Formatter};
+
+use itertools::Itertools;
+use nom::character::complete::u64;
+use nom::combinator::verify;
+use nom::error::{context, ContextError, ErrorKind, FromExternalError, ParseError};
+use nom::{Input, Parser};
+
+// Error reporting for the nom parsers. Parsers return IResult, whose error remembers where
+// parsing failed and what would have been accepted there, and run turns that into a Report with
+// the line, column and source line of the failure.
+
+pub type IResult<I, O> = nom::IResult<I, O, Error<I>>;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Expected {
+    Char(char),
+    Kind(ErrorKind),
+    Context(&'static str),
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expected::Char('\n') => write!(f, "a newline"),
+            Expected::Char(c) => write!(f, "{c:?}"),
+            Expected::Context(c) => write!(f, "{c}"),
+            Expected::Kind(k) => write!(
+                f,
+                "{}",
+                match k {
+                    ErrorKind::Digit => "a number",
+                    ErrorKind::Alpha => "a letter",
+                    ErrorKind::AlphaNumeric => "a letter or digit",
+                    ErrorKind::Space => "a space",
+                    ErrorKind::MultiSpace => "whitespace",
+                    ErrorKind::CrLf => "a newline",
+                    ErrorKind::Eof => "the end of the input",
+                    ErrorKind::Tag => "a keyword",
+                    ErrorKind::IsNot | ErrorKind::TakeWhile1 => "some text",
+                    ErrorKind::MapRes | ErrorKind::MapOpt | ErrorKind::Verify => "a valid value",
+                    ErrorKind::Many1 | ErrorKind::SeparatedNonEmptyList => "at least one item",
+                    _ => "something else",
+                }
+            ),
+        }
+    }
+}
+
+// A failure to parse: the remaining input where it happened, and what was expected there
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Error<I> {
+    pub input: I,
+    pub expected: Vec<Expected>,
+}
+
+impl<I: Input> ParseError<I> for Error<I> {
+    fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+        Error {
+            input,
+            expected: vec![Expected::Kind(kind)],
+        }
+    }
+
+    // The combinator that gave up is less informative than the parser that actually failed
+    fn append(_: I, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: I, c: char) -> Self {
+        Error {
+            input,
+            expected: vec![Expected::Char(c)],
+        }
+    }
+
+    // Of two alternatives, the one that got furthest is the most relevant. If both failed at the
+    // same place, either would have been accepted there.
+    fn or(mut self, other: Self) -> Self {
+        match self.input.input_len().cmp(&other.input.input_len()) {
+            std::cmp::Ordering::Less => self,
+            std::cmp::Ordering::Greater => other,
+            std::cmp::Ordering::Equal => {
+                self.expected.extend(other.expected);
+                self
+            }
+        }
+    }
+}
+
+impl<I> ContextError<I> for Error<I> {
+    fn add_context(_: I, ctx: &'static str, mut other: Self) -> Self {
+        other.expected = vec![Expected::Context(ctx)];
+        other
+    }
+}
+
+impl<I: Input, E> FromExternalError<I, E> for Error<I> {
+    fn from_external_error(input: I, kind: ErrorKind, _: E) -> Self {
+        Self::from_error_kind(input, kind)
+    }
+}
+
+// A parse error located in the original input
+#[derive(Clone, Eq, PartialEq)]
+pub struct Report {
+    // Both 1-indexed
+    pub line: usize,
+    pub column: usize,
+    pub source_line: String,
+    pub expected: String,
+}
+
+impl Report {
+    // Locate the remaining input rest within input. Usually rest is a suffix of input, but
+    // parsers like map_parser fail on a slice in the middle of it, so find it by address.
+    pub fn new(input: &str, rest: &str, expected: String) -> Report {
+        let offset = (rest.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|&offset| offset <= input.len())
+            .unwrap_or_else(|| input.len().saturating_sub(rest.len()));
+        let before = &input[..input.floor_char_boundary(offset)];
+        let line_start = before.rfind('\n').map_or(0, |n| n + 1);
+        let source_line = input[line_start..]
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        Report {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            source_line,
+            expected,
+        }
+    }
+
+    fn from_error(input: &str, e: Error<&str>) -> Report {
+        let expected = e
+            .expected
+            .iter()
+            .unique()
+            .map(Expected::to_string)
+            .join(" or ");
+        Report::new(input, e.input, format!("expected {expected}"))
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let number = self.line.to_string();
+        let margin = " ".repeat(number.len());
+        writeln!(
+            f,
+            "Invalid input at line {}, column {}: {}",
+            self.line, self.column, self.expected
+        )?;
+        writeln!(f, "{margin} |")?;
+        writeln!(f, "{number} | {}", self.source_line)?;
+        write!(f, "{margin} | {}^", " ".repeat(self.column - 1))
+    }
+}
+
+// main prints returned errors with Debug, so make that readable too
+impl Debug for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl std::error::Error for Report {}
+
+// Whether run fails if the parser leaves anything but whitespace. Strict is the default, since
+// otherwise a malformed line would just end e.g. a separated_list1 early, silently truncating the
+// input.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum Strictness {
+    #[default]
+    Strict,
+    Lenient,
+}
+
+// Run parser on input, reporting any error with its location
+pub fn run<'a, O>(
+    parser: impl Parser<&'a str, Output = O, Error = Error<&'a str>>,
+    input: &'a str,
+) -> Result<O, Report> {
+    run_with(parser, input, Strictness::Strict)
+}
+
+// Like run, but only failing on leftover input if strictness says so
+pub fn run_with<'a, O>(
+    mut parser: impl Parser<&'a str, Output = O, Error = Error<&'a str>>,
+    input: &'a str,
+    strictness: Strictness,
+) -> Result<O, Report> {
+    let (rest, out) = parser.parse(input).map_err(|e| match e {
+        nom::Err::Error(e) | nom::Err::Failure(e) => Report::from_error(input, e),
+        nom::Err::Incomplete(_) => Report::new(input, "", "expected more input".to_string()),
+    })?;
+    if strictness == Strictness::Strict && !rest.trim().is_empty() {
+        // Point at the first thing that isn't whitespace, since that's what stopped the parser
+        let rest = rest.trim_start_matches([' ', '\t', '\r', '\n']);
+        let expected = "parsing stopped here, expected the end of the input".to_string();
+        return Err(Report::new(input, rest, expected));
+    }
+    Ok(out)
+}
+
+// A non-negative coordinate small enough that squared distances and rectangle areas between
+// points fit in a u64
+pub fn coordinate(input: &str) -> IResult<&str, u64> {
+    context("a coordinate below 2^31", verify(u64, |&c| c < 1 << 31)).parse(input)
+}
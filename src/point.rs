@@ -0,0 +1,258 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+
+use nom::character::complete::char;
+use nom::Parser;
+use num::traits::{CheckedAdd, CheckedMul, Zero};
+use num::PrimInt;
+
+use crate::grid::{IPoint, UPoint};
+use crate::parse::{Error, IResult};
+
+// Points with N integer coordinates, for puzzles in any number of dimensions. Arithmetic is per
+// coordinate, and overflows like the coordinates themselves, while distances are checked.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Point<T, const N: usize>(pub [T; N]);
+
+pub type Point2<T> = Point<T, 2>;
+pub type Point3<T> = Point<T, 3>;
+
+// The integer types a point can have, with the unsigned type that holds their differences
+pub trait Coordinate: Copy + Ord {
+    type Unsigned: PrimInt + num::Unsigned;
+
+    fn abs_diff(self, other: Self) -> Self::Unsigned;
+}
+
+macro_rules! coordinate {
+    ($($t:ty => $u:ty),*) => {
+        $(impl Coordinate for $t {
+            type Unsigned = $u;
+
+            fn abs_diff(self, other: $t) -> $u {
+                <$t>::abs_diff(self, other)
+            }
+        })*
+    };
+}
+
+coordinate!(
+    u8 => u8, u16 => u16, u32 => u32, u64 => u64, u128 => u128, usize => usize,
+    i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize
+);
+
+impl<T, const N: usize> Point<T, N> {
+    pub fn new(coordinates: [T; N]) -> Point<T, N> {
+        Point(coordinates)
+    }
+
+    // The same point with every coordinate converted, or None if one of them doesn't fit
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Point<U, N>> {
+        let cast: Vec<U> = self
+            .0
+            .into_iter()
+            .map(|c| U::try_from(c).ok())
+            .collect::<Option<_>>()?;
+        cast.try_into().ok().map(Point)
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Point<U, N> {
+        Point(self.0.map(f))
+    }
+}
+
+impl<T: Coordinate, const N: usize> Point<T, N> {
+    // How far apart the points are along each axis
+    pub fn abs_diff(self, other: Point<T, N>) -> Point<T::Unsigned, N> {
+        Point(std::array::from_fn(|i| self.0[i].abs_diff(other.0[i])))
+    }
+
+    // The sum of the distances along each axis, or None if it overflows
+    pub fn manhattan(self, other: Point<T, N>) -> Option<T::Unsigned> {
+        self.abs_diff(other)
+            .0
+            .into_iter()
+            .try_fold(T::Unsigned::zero(), |sum, d| sum.checked_add(&d))
+    }
+
+    // The largest distance along an axis, which is the number of king's moves between them
+    pub fn chebyshev(self, other: Point<T, N>) -> T::Unsigned {
+        self.abs_diff(other)
+            .0
+            .into_iter()
+            .max()
+            .unwrap_or(T::Unsigned::zero())
+    }
+
+    // The square of the straight line distance, which orders points like the distance itself
+    // without leaving the integers, or None if it overflows
+    pub fn squared_euclidean(self, other: Point<T, N>) -> Option<T::Unsigned> {
+        self.abs_diff(other)
+            .0
+            .into_iter()
+            .try_fold(T::Unsigned::zero(), |sum, d| {
+                sum.checked_add(&d.checked_mul(&d)?)
+            })
+    }
+}
+
+impl<T: Copy> Point<T, 2> {
+    pub fn x(&self) -> T {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> T {
+        self.0[1]
+    }
+}
+
+impl<T: Copy> Point<T, 3> {
+    pub fn x(&self) -> T {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> T {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> T {
+        self.0[2]
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Point<T, N> {
+    fn from(coordinates: [T; N]) -> Point<T, N> {
+        Point(coordinates)
+    }
+}
+
+impl From<UPoint> for Point<usize, 2> {
+    fn from(p: UPoint) -> Point<usize, 2> {
+        Point([p.x, p.y])
+    }
+}
+
+impl From<Point<usize, 2>> for UPoint {
+    fn from(Point([x, y]): Point<usize, 2>) -> UPoint {
+        UPoint { x, y }
+    }
+}
+
+impl From<IPoint> for Point<isize, 2> {
+    fn from(p: IPoint) -> Point<isize, 2> {
+        Point([p.x, p.y])
+    }
+}
+
+impl From<Point<isize, 2>> for IPoint {
+    fn from(Point([x, y]): Point<isize, 2>) -> IPoint {
+        IPoint { x, y }
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Point<T, N> {
+    type Output = T;
+
+    fn index(&self, axis: usize) -> &T {
+        &self.0[axis]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for Point<T, N> {
+    fn index_mut(&mut self, axis: usize) -> &mut T {
+        &mut self.0[axis]
+    }
+}
+
+impl<T: Copy + Add<Output = T>, const N: usize> Add for Point<T, N> {
+    type Output = Point<T, N>;
+
+    fn add(self, other: Point<T, N>) -> Point<T, N> {
+        Point(std::array::from_fn(|i| self.0[i] + other.0[i]))
+    }
+}
+
+impl<T: Copy + Sub<Output = T>, const N: usize> Sub for Point<T, N> {
+    type Output = Point<T, N>;
+
+    fn sub(self, other: Point<T, N>) -> Point<T, N> {
+        Point(std::array::from_fn(|i| self.0[i] - other.0[i]))
+    }
+}
+
+impl<T: Copy + Add<Output = T>, const N: usize> AddAssign for Point<T, N> {
+    fn add_assign(&mut self, other: Point<T, N>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>, const N: usize> SubAssign for Point<T, N> {
+    fn sub_assign(&mut self, other: Point<T, N>) {
+        *self = *self - other;
+    }
+}
+
+impl<T: Neg<Output = T>, const N: usize> Neg for Point<T, N> {
+    type Output = Point<T, N>;
+
+    fn neg(self) -> Point<T, N> {
+        self.map(T::neg)
+    }
+}
+
+// Scaling by a number
+impl<T: Copy + Mul<Output = T>, const N: usize> Mul<T> for Point<T, N> {
+    type Output = Point<T, N>;
+
+    fn mul(self, k: T) -> Point<T, N> {
+        self.map(|c| c * k)
+    }
+}
+
+// Written like the input, as x,y,z
+impl<T: Display, const N: usize> Display for Point<T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, c) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{c}")?;
+        }
+        Ok(())
+    }
+}
+
+// N coordinates separated by commas, like 1,2,3
+pub fn comma_separated<'a, T, const N: usize>(
+    mut coordinate: impl Parser<&'a str, Output = T, Error = Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Point<T, N>> {
+    move |mut input| {
+        let mut coordinates = Vec::with_capacity(N);
+        for i in 0..N {
+            if i > 0 {
+                (input, _) = char(',').parse(input)?;
+            }
+            let (rest, c) = coordinate.parse(input)?;
+            coordinates.push(c);
+            input = rest;
+        }
+        let Ok(coordinates) = coordinates.try_into() else {
+            unreachable!("exactly N coordinates were parsed")
+        };
+        Ok((input, Point(coordinates)))
+    }
+}
+
+// x,y
+pub fn xy<'a, T>(
+    coordinate: impl Parser<&'a str, Output = T, Error = Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Point2<T>> {
+    comma_separated(coordinate)
+}
+
+// x,y,z
+pub fn xyz<'a, T>(
+    coordinate: impl Parser<&'a str, Output = T, Error = Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Point3<T>> {
+    comma_separated(coordinate)
+}
@@ -0,0 +1,121 @@
+use std::num::NonZero;
+
+use num::Integer;
+
+// Stable versions of nightly-only std features, as extension traits with the same method names.
+// Until the std versions are stabilized, calling them trips the unstable_name_collisions lint, so
+// callers allow it. Once they are, the inherent std methods take precedence and these traits can
+// be dropped one by one.
+
+pub trait IteratorExt: Iterator + Sized {
+    // Like Iterator::map_windows: call f on every window of N consecutive items
+    fn map_windows<F, R, const N: usize>(self, f: F) -> MapWindows<Self, F, N>
+    where
+        F: FnMut(&[Self::Item; N]) -> R,
+    {
+        assert!(N > 0, "map_windows needs windows of at least one item");
+        MapWindows {
+            iter: self,
+            f,
+            window: Vec::with_capacity(N),
+        }
+    }
+
+    // Like Iterator::advance_by: skip n items, or report how many were missing
+    fn advance_by(&mut self, n: usize) -> Result<(), NonZero<usize>> {
+        for i in 0..n {
+            if self.next().is_none() {
+                // i < n, so n - i is never 0
+                return Err(NonZero::new(n - i).expect("n - i > 0"));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<I: Iterator> IteratorExt for I {}
+
+pub struct MapWindows<I: Iterator, F, const N: usize> {
+    iter: I,
+    f: F,
+    window: Vec<I::Item>,
+}
+
+impl<I, F, R, const N: usize> Iterator for MapWindows<I, F, N>
+where
+    I: Iterator,
+    F: FnMut(&[I::Item; N]) -> R,
+{
+    type Item = R;
+
+    fn next(&mut self) -> Option<R> {
+        if self.window.len() == N {
+            self.window.remove(0);
+        }
+        while self.window.len() < N {
+            self.window.push(self.iter.next()?);
+        }
+        let window = <&[I::Item; N]>::try_from(self.window.as_slice())
+            .expect("the window has exactly N items");
+        Some((self.f)(window))
+    }
+}
+
+pub trait DivFloor {
+    // Like i64::div_floor: the quotient rounded towards negative infinity
+    fn div_floor(self, rhs: Self) -> Self;
+}
+
+impl<T: Integer> DivFloor for T {
+    fn div_floor(self, rhs: T) -> T {
+        Integer::div_floor(&self, &rhs)
+    }
+}
+
+pub trait MinMax: Ord + Sized {
+    // Like std::cmp::minmax: the smaller and the larger of self and other, self first if they are
+    // equal
+    fn minmax(self, other: Self) -> [Self; 2] {
+        if other < self {
+            [other, self]
+        } else {
+            [self, other]
+        }
+    }
+}
+
+impl<T: Ord> MinMax for T {}
+
+pub trait StringExt {
+    // Like String::into_chars: the chars of an owned string
+    fn into_chars(self) -> IntoChars;
+}
+
+impl StringExt for String {
+    fn into_chars(self) -> IntoChars {
+        IntoChars { s: self, front: 0 }
+    }
+}
+
+pub struct IntoChars {
+    s: String,
+    front: usize,
+}
+
+impl Iterator for IntoChars {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.s[self.front..].chars().next()?;
+        self.front += c.len_utf8();
+        Some(c)
+    }
+}
+
+impl DoubleEndedIterator for IntoChars {
+    fn next_back(&mut self) -> Option<char> {
+        let c = self.s[self.front..].chars().next_back()?;
+        self.s.truncate(self.s.len() - c.len_utf8());
+        Some(c)
+    }
+}
@@ -0,0 +1,273 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+use num::Zero;
+
+// Shortest path searches over any state type. The graph is given by a successor function, so it
+// never has to be built up front, and the search stops at the first state that is_goal accepts,
+// which can be any number of states. Every search can start from several states at once.
+//
+// The searches remember the states they have seen in a Visited, which is a HashMap by default.
+// The _with variants take any other: a BTreeMap for states that aren't Hash, Indexed for states
+// that map to small integers (like points on a grid), or Unvisited for trees, where no state can
+// be reached twice and remembering them would be a waste.
+
+// The cheapest way to a goal: its cost, and the states from a start to the goal
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Path<S, C> {
+    pub cost: C,
+    pub states: Vec<S>,
+}
+
+impl<S, C> Path<S, C> {
+    pub fn goal(&self) -> &S {
+        self.states.last().expect("a path has at least one state")
+    }
+}
+
+// Where the searches remember which states they have seen, by their position in the search
+pub trait Visited<S> {
+    fn get(&self, s: &S) -> Option<usize>;
+    fn insert(&mut self, s: S, index: usize);
+}
+
+impl<S: Hash + Eq> Visited<S> for HashMap<S, usize> {
+    fn get(&self, s: &S) -> Option<usize> {
+        HashMap::get(self, s).copied()
+    }
+
+    fn insert(&mut self, s: S, index: usize) {
+        HashMap::insert(self, s, index);
+    }
+}
+
+impl<S: Ord> Visited<S> for BTreeMap<S, usize> {
+    fn get(&self, s: &S) -> Option<usize> {
+        BTreeMap::get(self, s).copied()
+    }
+
+    fn insert(&mut self, s: S, index: usize) {
+        BTreeMap::insert(self, s, index);
+    }
+}
+
+// For states that key maps to distinct small integers, e.g. y * width + x for grid points
+pub struct Indexed<F> {
+    key: F,
+    seen: Vec<Option<usize>>,
+}
+
+impl<F> Indexed<F> {
+    pub fn new(key: F) -> Indexed<F> {
+        Indexed {
+            key,
+            seen: Vec::new(),
+        }
+    }
+}
+
+impl<S, F: Fn(&S) -> usize> Visited<S> for Indexed<F> {
+    fn get(&self, s: &S) -> Option<usize> {
+        self.seen.get((self.key)(s)).copied().flatten()
+    }
+
+    fn insert(&mut self, s: S, index: usize) {
+        let key = (self.key)(&s);
+        if key >= self.seen.len() {
+            self.seen.resize(key + 1, None);
+        }
+        self.seen[key] = Some(index);
+    }
+}
+
+// Remembers nothing, for searches on trees. On a graph with cycles the searches won't end.
+pub struct Unvisited;
+
+impl<S> Visited<S> for Unvisited {
+    fn get(&self, _: &S) -> Option<usize> {
+        None
+    }
+
+    fn insert(&mut self, _: S, _: usize) {}
+}
+
+// Every state the search has reached, with the position of the state it was reached from
+struct Arena<S> {
+    nodes: Vec<(S, Option<usize>)>,
+}
+
+impl<S: Clone> Arena<S> {
+    fn push(&mut self, s: S, parent: Option<usize>) -> usize {
+        self.nodes.push((s, parent));
+        self.nodes.len() - 1
+    }
+
+    fn state(&self, index: usize) -> &S {
+        &self.nodes[index].0
+    }
+
+    fn path<C>(&self, mut index: usize, cost: C) -> Path<S, C> {
+        let mut states = vec![self.nodes[index].0.clone()];
+        while let Some(parent) = self.nodes[index].1 {
+            states.push(self.nodes[parent].0.clone());
+            index = parent;
+        }
+        states.reverse();
+        Path { cost, states }
+    }
+}
+
+// The path with the fewest steps from any of the starts to a goal
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Clone + Hash + Eq,
+    I: IntoIterator<Item = S>,
+{
+    bfs_with(HashMap::new(), starts, successors, is_goal)
+}
+
+pub fn bfs_with<S, I>(
+    mut visited: impl Visited<S>,
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, usize>>
+where
+    S: Clone,
+    I: IntoIterator<Item = S>,
+{
+    let mut arena = Arena { nodes: Vec::new() };
+    let mut queue = VecDeque::new();
+    for s in starts {
+        if visited.get(&s).is_none() {
+            let index = arena.push(s.clone(), None);
+            visited.insert(s, index);
+            queue.push_back((index, 0));
+        }
+    }
+
+    while let Some((index, steps)) = queue.pop_front() {
+        if is_goal(arena.state(index)) {
+            return Some(arena.path(index, steps));
+        }
+        for next in successors(&arena.state(index).clone()) {
+            if visited.get(&next).is_none() {
+                let n = arena.push(next.clone(), Some(index));
+                visited.insert(next, n);
+                queue.push_back((n, steps + 1));
+            }
+        }
+    }
+    None
+}
+
+// The cheapest path from any of the starts to a goal, where successors gives the cost of each
+// step, which must not be negative
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Hash + Eq,
+    C: Copy + Ord + Zero + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar_with(HashMap::new(), starts, successors, |_| C::zero(), is_goal)
+}
+
+pub fn dijkstra_with<S, C, I>(
+    visited: impl Visited<S>,
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone,
+    C: Copy + Ord + Zero + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar_with(visited, starts, successors, |_| C::zero(), is_goal)
+}
+
+// Like dijkstra, but heuristic estimates the cost from a state to the nearest goal, and the search
+// explores the most promising states first. The path is the cheapest as long as the heuristic
+// never overestimates and never drops by more than the cost of a step.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    heuristic: impl FnMut(&S) -> C,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone + Hash + Eq,
+    C: Copy + Ord + Zero + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar_with(HashMap::new(), starts, successors, heuristic, is_goal)
+}
+
+pub fn astar_with<S, C, I>(
+    mut visited: impl Visited<S>,
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S, C>>
+where
+    S: Clone,
+    C: Copy + Ord + Zero + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut arena = Arena { nodes: Vec::new() };
+    // The cheapest known cost of each state in the arena
+    let mut costs: Vec<C> = Vec::new();
+    // Ordered by the estimated total cost, and then by the cost so far
+    let mut queue = BinaryHeap::new();
+    for s in starts {
+        if visited.get(&s).is_none() {
+            let estimate = heuristic(&s);
+            let index = arena.push(s.clone(), None);
+            visited.insert(s, index);
+            costs.push(C::zero());
+            queue.push(Reverse((estimate, C::zero(), index)));
+        }
+    }
+
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        // A cheaper way here was found after this one was queued
+        if cost > costs[index] {
+            continue;
+        }
+        let state = arena.state(index).clone();
+        if is_goal(&state) {
+            return Some(arena.path(index, cost));
+        }
+        for (next, step) in successors(&state) {
+            let next_cost = cost + step;
+            let n = match visited.get(&next) {
+                Some(n) if costs[n] <= next_cost => continue,
+                Some(n) => {
+                    arena.nodes[n].1 = Some(index);
+                    costs[n] = next_cost;
+                    n
+                }
+                None => {
+                    let n = arena.push(next.clone(), Some(index));
+                    visited.insert(next, n);
+                    costs.push(next_cost);
+                    n
+                }
+            };
+            let estimate = next_cost + heuristic(arena.state(n));
+            queue.push(Reverse((estimate, next_cost, n)));
+        }
+    }
+    None
+}
@@ -0,0 +1,102 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use crate::parse::Strictness;
+
+// A solution to one day's puzzle. The input is parsed once and then shared by both parts, so the
+// parts can be run (and timed) independently of each other.
+pub trait Solution {
+    type Input;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    // Parse the input, failing on leftover input only if strictness is Strict
+    fn parse_with(input: &str, strictness: Strictness) -> Result<Self::Input, Box<dyn Error>>;
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne, Box<dyn Error>>;
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo, Box<dyn Error>>;
+
+    fn parse(input: &str) -> Result<Self::Input, Box<dyn Error>> {
+        Self::parse_with(input, Strictness::Strict)
+    }
+}
+
+// The answer to a part that doesn't have a puzzle, i.e. the second half of the last day
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct NoAnswer;
+
+impl Display for NoAnswer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "-")
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("No such part: {s}")),
+        }
+    }
+}
+
+// A parsed input of any day. The answers are rendered as text so that days with different answer
+// types can be run side by side.
+pub trait Parsed {
+    fn solve(&self, part: Part) -> Result<String, Box<dyn Error>>;
+}
+
+struct ParsedInput<S: Solution> {
+    input: S::Input,
+    solution: PhantomData<S>,
+}
+
+impl<S: Solution> Parsed for ParsedInput<S> {
+    fn solve(&self, part: Part) -> Result<String, Box<dyn Error>> {
+        match part {
+            Part::One => S::part_one(&self.input).map(|a| a.to_string()),
+            Part::Two => S::part_two(&self.input).map(|a| a.to_string()),
+        }
+    }
+}
+
+pub type ParseFn = fn(&str, Strictness) -> Result<Box<dyn Parsed>, Box<dyn Error>>;
+
+pub fn parse_dyn<S: Solution + 'static>(
+    input: &str,
+    strictness: Strictness,
+) -> Result<Box<dyn Parsed>, Box<dyn Error>> {
+    Ok(Box::new(ParsedInput::<S> {
+        input: S::parse_with(input, strictness)?,
+        solution: PhantomData,
+    }))
+}
@@ -0,0 +1,78 @@
+// The answers file: parsing, and writing it back without losing anything
+use aoclib::answers::{Answers, InputFile, Key};
+use aoclib::solution::Part;
+
+fn key(day: u8, part: Part, input: InputFile) -> Key {
+    Key { day, input, part }
+}
+
+const MANIFEST: &str = "\
+# Checked by hand
+01 1 input 3
+01 2 input 6
+
+# From the puzzle text
+01 1 example 1 3
+02 2 input 4174379265
+# The end
+";
+
+#[test]
+fn parses_answers_and_skips_comments() {
+    let answers = Answers::parse(MANIFEST).unwrap();
+    assert_eq!(answers.iter().count(), 4);
+    assert_eq!(answers.get(&key(1, Part::Two, InputFile::Real)), Some("6"));
+    assert_eq!(
+        answers.get(&key(1, Part::One, InputFile::Example(1))),
+        Some("3")
+    );
+    assert_eq!(answers.get(&key(2, Part::One, InputFile::Real)), None);
+}
+
+#[test]
+fn round_trips_with_comments() {
+    let answers = Answers::parse(MANIFEST).unwrap();
+    assert_eq!(answers.to_string(), MANIFEST);
+    assert_eq!(
+        Answers::parse(&answers.to_string()).unwrap().to_string(),
+        MANIFEST
+    );
+}
+
+#[test]
+fn keeps_comments_with_their_answer_when_recording() {
+    let mut answers = Answers::parse("# Day 2\n02 1 input 7\n").unwrap();
+    answers.insert(key(1, Part::One, InputFile::Real), "3".to_string());
+    answers.insert(key(2, Part::One, InputFile::Real), "8".to_string());
+    assert_eq!(answers.to_string(), "01 1 input 3\n# Day 2\n02 1 input 8\n");
+}
+
+#[test]
+fn accepts_crlf_and_a_missing_final_newline() {
+    let crlf = MANIFEST.replace('\n', "\r\n");
+    assert_eq!(Answers::parse(&crlf).unwrap().to_string(), MANIFEST);
+    for input in [
+        "01 1 input 3\n# comment",
+        "01 1 input 3\n",
+        "01 1 input 3",
+        "# only\n\n",
+    ] {
+        let answers = Answers::parse(input).unwrap_or_else(|e| panic!("{input:?}: {e}"));
+        let mut expected = input.to_string();
+        if !expected.ends_with('\n') {
+            expected.push('\n');
+        }
+        assert_eq!(answers.to_string(), expected);
+    }
+    assert_eq!(Answers::parse("").unwrap().to_string(), "");
+}
+
+#[test]
+fn rejects_malformed_lines() {
+    let error = Answers::parse("01 1 input 3\n01 3 input 4\n").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid answers: could not parse line \"01 3 input 4\""
+    );
+    assert!(Answers::parse("01 1 input \n").is_err());
+}
@@ -0,0 +1,95 @@
+// Benchmark statistics, and saving and loading benchmark results
+use std::time::Duration;
+
+use aoclib::answers::InputFile;
+use aoclib::bench::{measure, Key, Phase, Results, Stats};
+use aoclib::solution::Part;
+
+fn ms(ms: &[u64]) -> Vec<Duration> {
+    ms.iter().map(|&m| Duration::from_millis(m)).collect()
+}
+
+#[test]
+fn stats_of_samples() {
+    let stats = |samples: &[u64]| Stats::from_samples(&mut ms(samples));
+    assert_eq!(
+        stats(&[5, 1, 3]),
+        Some(Stats {
+            min: Duration::from_millis(1),
+            median: Duration::from_millis(3),
+            max: Duration::from_millis(5),
+        })
+    );
+    // With an even number of samples, the upper of the middle two
+    assert_eq!(
+        stats(&[4, 1, 2, 3]).unwrap().median,
+        Duration::from_millis(3)
+    );
+    assert_eq!(
+        stats(&[7]),
+        Some(Stats {
+            min: Duration::from_millis(7),
+            median: Duration::from_millis(7),
+            max: Duration::from_millis(7),
+        })
+    );
+    assert_eq!(stats(&[]), None);
+}
+
+#[test]
+fn measure_runs_at_least_once() {
+    let mut runs = 0;
+    let (out, _) = measure(0, || {
+        runs += 1;
+        Ok(runs)
+    })
+    .unwrap();
+    assert_eq!((out, runs), (1, 1));
+    let (out, _) = measure(5, || {
+        runs += 1;
+        Ok(runs)
+    })
+    .unwrap();
+    assert_eq!((out, runs), (6, 6));
+}
+
+fn results() -> Results {
+    let mut results = Results::default();
+    let stats = |min, median, max| Stats {
+        min: Duration::from_nanos(min),
+        median: Duration::from_nanos(median),
+        max: Duration::from_nanos(max),
+    };
+    results.insert(
+        Key {
+            day: 2,
+            input: InputFile::Real,
+            phase: Phase::Solve(Part::Two),
+        },
+        stats(10, 20, 30),
+    );
+    results.insert(
+        Key {
+            day: 2,
+            input: InputFile::Example(1),
+            phase: Phase::Parse,
+        },
+        stats(1, 1, 2),
+    );
+    results
+}
+
+#[test]
+fn results_round_trip() {
+    let text = results().to_string();
+    assert_eq!(text, "02 input part2 10 20 30\n02 example 1 parse 1 1 2\n");
+    assert_eq!(Results::parse(&text).unwrap().to_string(), text);
+}
+
+#[test]
+fn results_skip_blank_lines_and_comments() {
+    let text = "02 input part2 10 20 30\n\n# saved by hand\r\n02 example 1 parse 1 1 2";
+    let parsed = Results::parse(text).unwrap();
+    assert_eq!(parsed.to_string(), results().to_string());
+    assert!(Results::parse("02 input part3 1 2 3\n").is_err());
+}
@@ -0,0 +1,131 @@
+// The aoc runner, run as a separate process like a user would
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use serde_json::{json, Value};
+
+fn aoc(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .expect("could not run aoc")
+}
+
+// A file in the temporary directory, unique to this test
+fn temp_input(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("aoc-cli-{}-{name}", std::process::id()));
+    fs::write(&path, contents).expect("could not write the input");
+    path
+}
+
+#[test]
+fn lenient_ignores_leftover_input() {
+    let path = temp_input("leftover", "11-22,95-115\n!garbage\n");
+    let path = path.to_str().unwrap();
+    let strict = aoc(&["--day", "2", "--part", "1", "--input", path]);
+    assert!(!strict.status.success());
+    assert!(String::from_utf8_lossy(&strict.stderr).contains("parsing stopped here"));
+    let lenient = aoc(&["--day", "2", "--part", "1", "--input", path, "--lenient"]);
+    assert!(lenient.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&lenient.stdout),
+        "Day 02, part 1: 132\n"
+    );
+    fs::remove_file(path).unwrap();
+}
+
+// One record per line of --json output
+fn records(output: &Output) -> Vec<Value> {
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap_or_else(|e| panic!("{line:?}: {e}")))
+        .collect()
+}
+
+// The names of the diagnostics in a record
+fn diagnostic_names(record: &Value) -> Vec<&str> {
+    record["diagnostics"]
+        .as_array()
+        .expect("diagnostics is an array")
+        .iter()
+        .map(|d| d["name"].as_str().expect("diagnostics have a name"))
+        .collect()
+}
+
+#[test]
+fn json_records_have_every_field() {
+    let output = aoc(&["--day", "9", "--example", "1", "--json"]);
+    assert!(output.status.success());
+    let records = records(&output);
+    assert_eq!(records.len(), 2);
+    for (record, (part, answer)) in records.iter().zip([(1, "50"), (2, "24")]) {
+        assert_eq!(record["day"], 9);
+        assert_eq!(record["part"], part);
+        assert_eq!(record["answer"], answer);
+        assert_eq!(record["input"], "examples/09/1");
+        for timing in ["parse_ns", "solve_ns"] {
+            assert!(record["timings"][timing].is_u64(), "{record}");
+        }
+    }
+    // Both parts share the parse
+    assert_eq!(
+        records[0]["timings"]["parse_ns"],
+        records[1]["timings"]["parse_ns"]
+    );
+    assert_eq!(
+        records[0]["diagnostics"],
+        json!([{ "name": "corners", "value": { "corners": [[2, 3], [11, 7]] } }])
+    );
+    assert_eq!(
+        records[1]["diagnostics"],
+        json!([{ "name": "considered", "value": { "considered": 7, "squares": 28 } }])
+    );
+}
+
+#[test]
+fn json_diagnostics_are_reset_between_parts_and_days() {
+    let output = aoc(&["--all", "--example", "1", "--json"]);
+    assert!(output.status.success());
+    for record in records(&output) {
+        let names = diagnostic_names(&record);
+        let expected: &[&str] = match (record["day"].as_u64(), record["part"].as_u64()) {
+            (Some(9), Some(1)) => &["corners"],
+            (Some(9), Some(2)) => &["considered"],
+            (Some(4), Some(2)) => {
+                assert!(names.iter().all(|&n| n == "stage"), "{record}");
+                assert!(!names.is_empty(), "{record}");
+                continue;
+            }
+            (Some(11), Some(2)) => &[
+                "svr_to_fft",
+                "svr_to_dac",
+                "dac_to_fft",
+                "dac_to_out",
+                "fft_to_dac",
+                "fft_to_out",
+            ],
+            _ => &[],
+        };
+        assert_eq!(names, expected, "{record}");
+    }
+}
+
+#[test]
+fn json_reports_errors_as_records() {
+    let path = temp_input("json-error", "11-22,95-115\n!garbage\n");
+    let output = aoc(&["--day", "2", "--input", path.to_str().unwrap(), "--json"]);
+    assert!(!output.status.success());
+    let records = records(&output);
+    assert_eq!(records.len(), 1);
+    let record = records[0].as_object().expect("the record is an object");
+    assert_eq!(record.len(), 2, "{record:?}");
+    assert_eq!(record["day"], 2);
+    let error = record["error"].as_str().expect("the error is a string");
+    assert!(
+        error.starts_with("Invalid input at line 2, column 1: parsing stopped here"),
+        "{error}"
+    );
+    fs::remove_file(path).unwrap();
+}
@@ -0,0 +1,19 @@
+// Test helpers shared by the integration tests
+use aoclib::dial::Turn;
+
+// Turn a dial of size positions one click at a time, as the oracle for Dial
+pub fn click(size: u64, position: u64, clicks: i64) -> Turn {
+    let mut position = position as i64;
+    let mut clicks_on_zero = 0;
+    for _ in 0..clicks.abs() {
+        position = (position + clicks.signum()).rem_euclid(size as i64);
+        if position == 0 {
+            clicks_on_zero += 1;
+        }
+    }
+    Turn {
+        position: position as u64,
+        landed_on_zero: position == 0,
+        clicks_on_zero,
+    }
+}
@@ -0,0 +1,56 @@
+// The dial's documented semantics, checked one click at a time
+use proptest::prelude::*;
+
+use aoclib::dial::Dial;
+
+mod common;
+use common::click;
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2000))]
+
+    #[test]
+    fn turns_match_clicking(
+        size in 1u64..20,
+        start in 0u64..40,
+        rotations in prop::collection::vec(-100i64..100, 0..20),
+    ) {
+        let mut dial = Dial::new(size, start);
+        let mut position = start % size;
+        for (turn, &clicks) in dial.clone().rotations(rotations.iter().copied()).zip(&rotations) {
+            prop_assert_eq!(turn, click(size, position, clicks));
+            position = turn.position;
+        }
+        // Rotating one at a time ends up in the same place
+        for &clicks in &rotations {
+            dial.rotate(clicks);
+        }
+        prop_assert_eq!(dial.position(), position);
+    }
+}
+
+#[test]
+fn zero_is_counted_when_landed_on_but_not_when_left() {
+    let mut dial = Dial::new(100, 5);
+    let landed = dial.rotate(-5);
+    assert!(landed.landed_on_zero);
+    assert_eq!(landed.clicks_on_zero, 1);
+    // Leaving 0 either way doesn't count it again
+    assert_eq!(dial.clone().rotate(-5).clicks_on_zero, 0);
+    assert_eq!(dial.clone().rotate(5).clicks_on_zero, 0);
+    // A full turn from 0 comes back to it once, and a standstill stays there
+    assert_eq!(dial.clone().rotate(-100).clicks_on_zero, 1);
+    let still = dial.rotate(0);
+    assert!(still.landed_on_zero);
+    assert_eq!(still.clicks_on_zero, 0);
+}
+
+#[test]
+fn big_rotations_pass_zero_once_a_turn() {
+    let mut dial = Dial::new(100, 50);
+    assert_eq!(dial.rotate(1000).clicks_on_zero, 10);
+    assert_eq!(dial.rotate(-1000).clicks_on_zero, 10);
+    assert_eq!(dial.rotate(i64::MIN).position, 42);
+    assert_eq!(Dial::new(1, 7).rotate(-3).clicks_on_zero, 3);
+    assert_eq!(Dial::new(100, 250).position(), 50);
+}
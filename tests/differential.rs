@@ -0,0 +1,167 @@
+// Compare the clever parts of the solutions against naive implementations on random inputs.
+// proptest shrinks any failure to a minimal counterexample.
+use std::collections::BTreeSet;
+
+use itertools::Itertools;
+use proptest::prelude::*;
+
+use aoclib::days::day02::{mobius, sum_invalids, sum_repetitions, Day02};
+use aoclib::days::day03::max_joltage;
+use aoclib::days::day05::merge_ranges;
+use aoclib::dial::Dial;
+use aoclib::solution::Solution;
+
+mod common;
+use common::click;
+
+// The clicks that leave the dial at 0, counted one click at a time
+fn naive_crossings(rots: &[i64], initial: u64, size: u64) -> u64 {
+    let mut position = initial;
+    rots.iter()
+        .map(|&r| {
+            let turn = click(size, position, r);
+            position = turn.position;
+            turn.clicks_on_zero
+        })
+        .sum()
+}
+
+fn is_repetition(id: impl ToString, n: usize) -> bool {
+    let s = id.to_string();
+    s.len().is_multiple_of(n) && s == s[..s.len() / n].repeat(n)
+}
+
+fn naive_sum_invalids(start: u64, end: u64, n: usize) -> u64 {
+    (start..=end).filter(|&id| is_repetition(id, n)).sum()
+}
+
+fn naive_any_repetition_u128(start: u128, end: u128) -> Option<u128> {
+    (start..=end)
+        .filter(|&id| (2..=id.to_string().len()).any(|n| is_repetition(id, n)))
+        .try_fold(0_u128, u128::checked_add)
+}
+
+fn naive_any_repetition(spans: &[(u64, u64)]) -> u64 {
+    spans
+        .iter()
+        .flat_map(|&(start, end)| start..=end)
+        .filter(|&id| (2..=id.to_string().len()).any(|n| is_repetition(id, n)))
+        .sum()
+}
+
+// Try every way of picking n batteries in order
+fn naive_max_joltage(bats: &[u32], n: usize) -> Option<u64> {
+    bats.iter()
+        .combinations(n)
+        .map(|c| c.into_iter().fold(0, |j, &b| 10 * j + b as u64))
+        .max()
+}
+
+fn covered(ranges: &[(u64, u64)]) -> BTreeSet<u64> {
+    ranges.iter().flat_map(|&(lo, hi)| lo..=hi).collect()
+}
+
+// A span of IDs with d digits at the start, and up to max_digits at the end
+fn span(max_digits: u32) -> impl Strategy<Value = (u64, u64)> {
+    (1..=max_digits, any::<u64>(), 0u64..3000, any::<bool>()).prop_map(
+        move |(d, offset, width, near_top)| {
+            let lo = 10u64.pow(d - 1);
+            let hi = 10u64.pow(d) - 1;
+            // Starting near the top of the range makes spans that cross into d + 1 digits
+            let start = if near_top {
+                hi - offset % (hi - lo + 1).min(3000)
+            } else {
+                lo + offset % (hi - lo + 1)
+            };
+            let end = (start + width).min(10u64.pow(max_digits) - 1);
+            (start, end)
+        },
+    )
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2000))]
+
+    #[test]
+    fn crossings_match_clicking(
+        rots in prop::collection::vec((-500i64..500).prop_filter("nonzero", |r| *r != 0), 0..20),
+        initial in 0u64..100,
+    ) {
+        let crossings = Dial::new(100, initial).rotations(rots.iter().copied()).map(|t| t.clicks_on_zero).sum::<u64>();
+        prop_assert_eq!(crossings, naive_crossings(&rots, initial, 100));
+    }
+
+    #[test]
+    fn sum_invalids_matches_enumeration((start, end) in span(12), n in 2usize..=6) {
+        prop_assert_eq!(sum_invalids(start, end, n as u32), Some(naive_sum_invalids(start, end, n)));
+    }
+
+    #[test]
+    fn sum_invalids_matches_enumeration_across_lengths(start in 0u64..1000, width in 0u64..20_000, n in 1usize..=6) {
+        let end = start + width;
+        prop_assert_eq!(sum_invalids(start, end, n as u32), Some(naive_sum_invalids(start, end, n)));
+    }
+
+    #[test]
+    fn inclusion_exclusion_matches_enumeration(spans in prop::collection::vec(span(10), 1..4)) {
+        prop_assert_eq!(Day02::part_two(&spans).unwrap(), naive_any_repetition(&spans));
+    }
+
+    // Windows of 20 digit u64s, and of u128s of any length, up to the largest of each
+    #[test]
+    fn repetitions_match_enumeration_for_long_ids(offset in 0u64..1000, width in 0u64..300, d in 20u32..=39, wide in any::<bool>()) {
+        let start = u64::MAX - offset;
+        let end = start + width.min(offset);
+        prop_assert_eq!(sum_repetitions(start, end), Some(naive_any_repetition(&[(start, end)])));
+        let end = if wide { u128::MAX - u128::from(offset) } else { 10u128.checked_pow(d).map_or(u128::MAX, |p| p - 1) - u128::from(offset) };
+        let start = end - u128::from(width);
+        prop_assert_eq!(sum_repetitions(start, end), naive_any_repetition_u128(start, end));
+    }
+
+    #[test]
+    fn greedy_joltage_matches_exhaustive(
+        bats in prop::collection::vec(0u32..10, 1..14),
+        n in 1usize..=14,
+    ) {
+        prop_assert_eq!(max_joltage(&bats, n as u32), naive_max_joltage(&bats, n));
+    }
+
+    #[test]
+    fn merged_ranges_cover_the_same_ids(
+        ranges in prop::collection::vec((0u64..200, 0u64..30).prop_map(|(lo, w)| (lo, lo + w)), 1..12),
+    ) {
+        let merged = merge_ranges(&ranges);
+        prop_assert_eq!(covered(&merged), covered(&ranges));
+        // The merged ranges must be sorted and disjoint, or counting their sizes would overcount
+        for ((_, a_hi), (b_lo, _)) in merged.iter().tuple_windows() {
+            prop_assert!(a_hi < b_lo);
+        }
+    }
+}
+
+#[test]
+fn mobius_of_small_numbers() {
+    let expected = [
+        1, -1, -1, 0, -1, 1, -1, 0, 0, 1, -1, 0, -1, 1, 1, 0, -1, 0, -1, 0,
+    ];
+    assert_eq!((1..=20).map(mobius).collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn repetitions_of_every_length_are_counted_once() {
+    // 1111111111 repeats a block of 1, 2 or 5 digits, and 11 repeats one of 1
+    assert_eq!(
+        sum_repetitions(1111111111_u64, 1111111111),
+        Some(1111111111)
+    );
+    assert_eq!(sum_repetitions(10_u64, 99), Some(11 * 45));
+    // The largest doubled 20 digit u64, and the u128 repetitions of one digit 39 times
+    let top = 1844674407_u64 * 10000000001;
+    assert_eq!(sum_invalids(top, u64::MAX, 2), Some(top));
+    assert_eq!(sum_repetitions(top + 1, u64::MAX), Some(0));
+    assert_eq!(sum_repetitions(0, u64::MAX), None);
+    let ones = (0..39).map(|i| 10_u128.pow(i)).sum::<u128>();
+    assert_eq!(sum_invalids(2 * ones + 1, u128::MAX, 39), Some(3 * ones));
+    assert_eq!(sum_invalids(ones, u128::MAX, 39), None);
+    assert_eq!(sum_repetitions(ones, ones), Some(ones));
+}
@@ -0,0 +1,73 @@
+// Runs every example in examples/DD/N against the expected answers in examples/DD/N.expected,
+// which has a line "P: ANSWER" for each part P that should be checked.
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoclib::days;
+use aoclib::diagnostics;
+use aoclib::parse::Strictness;
+use aoclib::solution::Part;
+
+fn examples() -> Result<Vec<(u8, PathBuf)>, Box<dyn Error>> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples");
+    let mut out = Vec::new();
+    for dir in fs::read_dir(root)? {
+        let dir = dir?.path();
+        let Some(day) = dir.file_name().and_then(|d| d.to_str()?.parse().ok()) else {
+            continue;
+        };
+        for file in fs::read_dir(&dir)? {
+            let file = file?.path();
+            if file.extension().is_none() {
+                out.push((day, file));
+            }
+        }
+    }
+    out.sort();
+    Ok(out)
+}
+
+fn expected(path: &Path) -> Result<Vec<(Part, String)>, Box<dyn Error>> {
+    let expected = fs::read_to_string(path.with_extension("expected"))?;
+    expected
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let (part, answer) = l.split_once(':').ok_or(format!("Invalid line {l:?}"))?;
+            Ok((part.trim().parse()?, answer.trim().to_string()))
+        })
+        .collect()
+}
+
+fn check(day: u8, path: &Path) -> Result<(), Box<dyn Error>> {
+    let expected = expected(path)?;
+    let day = days::get(day).ok_or("day is not registered")?;
+    let parsed = (day.parse)(&fs::read_to_string(path)?, Strictness::Strict)?;
+    for (part, expected) in expected {
+        let actual = parsed.solve(part)?;
+        if actual != expected {
+            return Err(format!("part {part}: expected {expected}, got {actual}").into());
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn examples_match_expected_answers() {
+    diagnostics::capture(true);
+    let examples = examples().expect("could not list the examples");
+    assert!(!examples.is_empty(), "no examples found");
+
+    let failures: Vec<String> = examples
+        .iter()
+        .filter_map(|(day, path)| {
+            check(*day, path)
+                .err()
+                .map(|e| format!("{}: {e}", path.display()))
+        })
+        .collect();
+    diagnostics::take();
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
@@ -0,0 +1,155 @@
+// Inputs the fuzz targets in fuzz/ found panics (or hangs) with. Each should now be either solved or
+// rejected with an error.
+use aoclib::days;
+use aoclib::diagnostics;
+use aoclib::parse::Strictness;
+use aoclib::solution::Part;
+
+use Outcome::*;
+
+enum Outcome {
+    // Parsing fails with an error containing this
+    Rejected(&'static str),
+    // The answer, or an error containing this, for each part
+    Solved([Result<&'static str, &'static str>; 2]),
+}
+
+const CASES: &[(u8, &str, Outcome)] = &[
+    // Amounts that overflowed an i32 position
+    (
+        1,
+        "R2147483647\nR2147483647\nL4294967295",
+        Solved([Ok("0"), Ok("85899346")]),
+    ),
+    // 0 has no logarithm, and big IDs overflowed
+    (2, "0-5", Solved([Ok("0"), Ok("0")])),
+    (
+        2,
+        "18446744073709551615-18446744073709551615",
+        Solved([Ok("0"), Ok("0")]),
+    ),
+    // A multi-byte character inside the digit parser
+    (3, "è2", Rejected("line 1, column 1: expected a number")),
+    // Ragged grid
+    (
+        4,
+        "@@@\n@@\n@@@",
+        Rejected("line 2, column 3: expected 3 cells like the first row"),
+    ),
+    // Backwards range, and a range with 2^64 IDs
+    (
+        5,
+        "10-4\n\n5",
+        Rejected("expected a range whose start is at most its end"),
+    ),
+    (
+        5,
+        "0-18446744073709551615\n\n5",
+        Solved([Ok("1"), Err("There are too many fresh IDs to count")]),
+    ),
+    // Products that don't fit in a u64
+    (
+        6,
+        "99999999999 99999999999\n99999999999 99999999999\n* *",
+        Rejected("In the transposed worksheet: Invalid input at line 11, column 4"),
+    ),
+    // A line wider than the first
+    (
+        7,
+        "..S..\n.....\n..^....^",
+        Rejected("line 3 is 8 wide, but the first line is only 5"),
+    ),
+    // Squared distances that don't fit in a u64
+    (
+        8,
+        "4294967296,0,0\n0,0,0",
+        Rejected("expected a coordinate below 2^31"),
+    ),
+    // Huge areas, and a huge extent that used to be scanned row by row
+    (
+        9,
+        "7,777777777777\n7,1\n11,1\n11,2",
+        Rejected("expected a coordinate below 2^31"),
+    ),
+    (
+        9,
+        "0,0\n0,2000000000\n2000000000,2000000000\n2000000000,0",
+        Solved([Ok("4000000004000000001"), Ok("4000000004000000001")]),
+    ),
+    // A button toggling a light that doesn't exist, lights that can't be turned on, and
+    // requirements that can't be met
+    (
+        10,
+        "[.#] (5) {1,1}",
+        Rejected("toggles light 5, but it only has 2 lights"),
+    ),
+    (
+        10,
+        "[.#] (0) {1,1}",
+        Solved([
+            Err("The lights of machine 1 can't be turned on"),
+            Err("The joltage requirements of machine 1 can't be met"),
+        ]),
+    ),
+    (
+        10,
+        "[#.] (0) {1,2}",
+        Solved([
+            Ok("1"),
+            Err("The joltage requirements of machine 1 can't be met"),
+        ]),
+    ),
+    // A cycle
+    (
+        11,
+        "you: aaa\naaa: bbb\nbbb: aaa out",
+        Solved([Err("bbb is part of a cycle"), Ok("0")]),
+    ),
+    // Fewer counts than presents
+    (
+        12,
+        "0:\n###\n#..\n###\n\n1:\n###\n.#.\n###\n\n4x4: 1",
+        Rejected("the 4x4 region has 1 counts, but there are 2 presents"),
+    ),
+    // A region too big to build a model for
+    (
+        12,
+        "0:\n###\n#..\n###\n\n1000000x1000000: 1",
+        Rejected("line 6, column 1: expected a region of at most 100000 cells"),
+    ),
+];
+
+#[test]
+fn fuzz_regressions_are_solved_or_rejected() {
+    diagnostics::capture(true);
+    for (number, input, outcome) in CASES {
+        let day = days::get(*number).expect("day is not registered");
+        let parsed = (day.parse)(input, Strictness::Strict);
+        match (outcome, parsed) {
+            (Rejected(expected), Err(e)) => {
+                let e = e.to_string();
+                assert!(e.contains(expected), "day {number:02}, {input:?}: {e}");
+            }
+            (Solved(expected), Ok(parsed)) => {
+                for (part, expected) in Part::ALL.into_iter().zip(expected) {
+                    match (expected, parsed.solve(part)) {
+                        (Ok(expected), Ok(answer)) => {
+                            assert_eq!(&answer, expected, "day {number:02}, {input:?}")
+                        }
+                        (Err(expected), Err(e)) => {
+                            let e = e.to_string();
+                            assert!(e.contains(expected), "day {number:02}, {input:?}: {e}");
+                        }
+                        (_, got) => panic!(
+                            "day {number:02}, {input:?}, {part:?}: got {:?}",
+                            got.map_err(|e| e.to_string())
+                        ),
+                    }
+                }
+            }
+            (Rejected(_), Ok(_)) => panic!("day {number:02} should have rejected {input:?}"),
+            (Solved(_), Err(e)) => panic!("day {number:02} should have parsed {input:?}: {e}"),
+        }
+    }
+    diagnostics::take();
+}
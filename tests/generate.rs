@@ -0,0 +1,61 @@
+// Every generator should produce input that its day parses (strictly) and, apart from day 12 which
+// needs a MILP solver, solves.
+use std::collections::HashSet;
+
+use aoclib::days::DAYS;
+use aoclib::diagnostics;
+use aoclib::generate;
+use aoclib::parse::Strictness;
+use aoclib::solution::Part;
+
+#[test]
+fn generated_inputs_are_deterministic() {
+    for day in DAYS {
+        let a = (day.generate)(&mut generate::rng(7), 5);
+        let b = (day.generate)(&mut generate::rng(7), 5);
+        assert_eq!(a, b, "day {:02}", day.number);
+    }
+}
+
+#[test]
+fn generated_inputs_parse_and_solve() {
+    diagnostics::capture(true);
+    for day in DAYS {
+        for seed in 0..5 {
+            let input = (day.generate)(&mut generate::rng(seed), 10);
+            let parsed = (day.parse)(&input, Strictness::Strict)
+                .unwrap_or_else(|e| panic!("day {:02}, seed {seed}: {e}\n{input}", day.number));
+            if day.number == 12 {
+                continue;
+            }
+            for part in Part::ALL {
+                if let Err(e) = parsed.solve(part) {
+                    panic!(
+                        "day {:02}, seed {seed}, part {part}: {e}\n{input}",
+                        day.number
+                    );
+                }
+            }
+        }
+    }
+    diagnostics::take();
+}
+
+#[test]
+fn large_sizes_fit() {
+    // More columns than the usual 100000 x coordinates
+    let input = generate::day09(&mut generate::rng(1), 100_000);
+    assert_eq!(input.lines().count(), 400_000);
+}
+
+#[test]
+fn day11_has_names_for_every_size() {
+    // More devices than there are three letter names
+    let input = generate::day11(&mut generate::rng(1), 20_000);
+    assert_eq!(input.lines().count(), 20_004);
+    let names: HashSet<_> = input
+        .lines()
+        .map(|l| l.split_once(':').unwrap().0)
+        .collect();
+    assert_eq!(names.len(), 20_004);
+}
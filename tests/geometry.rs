@@ -0,0 +1,328 @@
+// The polygon queries, checked against flood filling the outside of the polygon drawn on a grid,
+// and the largest rectangle, checked against trying every pair
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use aoclib::generate;
+use itertools::Itertools;
+
+use aoclib::geometry::{largest_rectangle, LargestRectangle, RectilinearPolygon};
+use aoclib::grid::{Connectivity, Grid, IPoint, Symmetry, UPoint};
+use aoclib::point::{Point, Point2};
+
+fn polygon(vertices: &[[isize; 2]]) -> RectilinearPolygon<isize> {
+    RectilinearPolygon::new(vertices.iter().map(|&v| Point(v)).collect()).expect("valid polygon")
+}
+
+// A polygon whose columns each cover one interval of rows, overlapping their neighbors' intervals
+fn random_polygon(rng: &mut StdRng) -> Vec<Point2<isize>> {
+    let columns = rng.random_range(1..=5);
+    let mut x = 0;
+    let (mut top, mut bottom) = (Vec::new(), Vec::new());
+    let mut last: Option<(i32, i32)> = None;
+    for _ in 0..columns {
+        let (t, b) = loop {
+            let t = rng.random_range(0..8);
+            let b = rng.random_range(t + 1..=8);
+            if last.is_none_or(|(lt, lb)| t.max(lt) < b.min(lb)) {
+                break (t, b);
+            }
+        };
+        let width = rng.random_range(1..=3);
+        top.extend([Point([x, t]), Point([x + width, t])]);
+        bottom.extend([Point([x, b]), Point([x + width, b])]);
+        x += width;
+        last = Some((t, b));
+    }
+    top.extend(bottom.into_iter().rev());
+    // Neighboring columns with the same top or bottom repeat a vertex
+    top.dedup();
+    // Turned or mirrored any way, and moved around
+    let symmetry = Symmetry::ALL[rng.random_range(0..8)];
+    let offset = IPoint {
+        x: rng.random_range(-3..3_i32) as isize,
+        y: rng.random_range(-3..3_i32) as isize,
+    };
+    top.into_iter()
+        .map(|p| p.try_cast().expect("small coordinates"))
+        .map(|p: Point2<isize>| (symmetry.apply(p.into()) + offset).into())
+        .collect()
+}
+
+const R: isize = 20;
+
+// The boundary drawn at twice the scale, with everything that can't be reached from outside
+// filled in, for coordinates in -R..R. Lattice points are at even coordinates, and the centers of
+// the unit squares between them at odd ones.
+fn brute_force(vertices: &[Point2<isize>]) -> Grid<bool> {
+    let double = |c: isize| (2 * (c + R)) as usize;
+    let mut walls = Grid::new(4 * R as usize, 4 * R as usize, false);
+    for (i, a) in vertices.iter().enumerate() {
+        let b = vertices[(i + 1) % vertices.len()];
+        for x in double(a.x().min(b.x()))..=double(a.x().max(b.x())) {
+            for y in double(a.y().min(b.y()))..=double(a.y().max(b.y())) {
+                walls[UPoint { x, y }] = true;
+            }
+        }
+    }
+    let outside = walls.flood_fill(UPoint { x: 0, y: 0 }, Connectivity::Four, |&w| !w);
+    Grid::from_fn(walls.width(), walls.height(), |p| !outside.contains(p))
+}
+
+// The lattice points in -R..R on both axes, and whether they are covered
+fn lattice(filled: &Grid<bool>) -> impl Iterator<Item = (UPoint, bool)> + '_ {
+    filled
+        .cells()
+        .filter(|(p, _)| p.x % 2 == 0 && p.y % 2 == 0)
+        .map(|(p, &c)| {
+            (
+                UPoint {
+                    x: p.x / 2,
+                    y: p.y / 2,
+                },
+                c,
+            )
+        })
+}
+
+fn from_lattice(p: UPoint) -> Point2<isize> {
+    Point([p.x as isize - R, p.y as isize - R])
+}
+
+#[test]
+fn queries_match_brute_force() {
+    let mut rng = generate::rng(9);
+    for _ in 0..200 {
+        let vertices = random_polygon(&mut rng);
+        let polygon = RectilinearPolygon::new(vertices.clone()).expect("valid polygon");
+        let filled = brute_force(&vertices);
+        let covered = Grid::from_fn(2 * R as usize, 2 * R as usize, |p| {
+            filled[UPoint {
+                x: 2 * p.x,
+                y: 2 * p.y,
+            }]
+        });
+        let at = from_lattice;
+        for (p, c) in lattice(&filled) {
+            assert_eq!(polygon.contains(at(p)), c, "{} in {vertices:?}", at(p));
+        }
+        for _ in 0..200 {
+            let mut corner = || UPoint {
+                x: rng.random_range(10..30),
+                y: rng.random_range(10..30),
+            };
+            let (a, b) = (corner(), corner());
+            let expected = (a.x.min(b.x)..=a.x.max(b.x))
+                .all(|x| (a.y.min(b.y)..=a.y.max(b.y)).all(|y| covered[UPoint { x, y }]));
+            assert_eq!(
+                polygon.contains_rect(at(a), at(b)),
+                expected,
+                "{} to {} in {vertices:?}",
+                at(a),
+                at(b)
+            );
+        }
+    }
+}
+
+#[test]
+fn covers_the_boundary_of_narrow_gaps() {
+    // A U whose arms are one apart: no lattice point is outside between them
+    let u = polygon(&[
+        [0, 0],
+        [1, 0],
+        [1, 3],
+        [2, 3],
+        [2, 0],
+        [3, 0],
+        [3, 4],
+        [0, 4],
+    ]);
+    assert!(u.contains_rect(Point([0, 0]), Point([3, 4])));
+    let wide = polygon(&[
+        [0, 0],
+        [1, 0],
+        [1, 3],
+        [3, 3],
+        [3, 0],
+        [4, 0],
+        [4, 4],
+        [0, 4],
+    ]);
+    assert!(!wide.contains_rect(Point([0, 0]), Point([4, 4])));
+    assert!(!wide.contains(Point([2, 2])));
+    assert!(wide.contains(Point([2, 3])));
+    assert!(!wide.contains(Point([5, 2])));
+}
+
+#[test]
+fn lattice_metrics_match_brute_force() {
+    let mut rng = generate::rng(22);
+    for _ in 0..200 {
+        let vertices = random_polygon(&mut rng);
+        let polygon = RectilinearPolygon::new(vertices.clone()).expect("valid polygon");
+        let filled = brute_force(&vertices);
+        let squares = filled
+            .cells()
+            .filter(|&(p, &c)| p.x % 2 == 1 && p.y % 2 == 1 && c)
+            .count();
+        let boundary = lattice(&filled)
+            .filter(|&(p, c)| {
+                c && vertices.iter().enumerate().any(|(i, a)| {
+                    let b = vertices[(i + 1) % vertices.len()];
+                    let p = from_lattice(p);
+                    (a.x().min(b.x())..=a.x().max(b.x())).contains(&p.x())
+                        && (a.y().min(b.y())..=a.y().max(b.y())).contains(&p.y())
+                })
+            })
+            .count();
+        let covered = lattice(&filled).filter(|&(_, c)| c).count();
+        assert_eq!(polygon.area(), Some(squares as u128), "{vertices:?}");
+        assert_eq!(
+            polygon.boundary_points(),
+            Some(boundary as u128),
+            "{vertices:?}"
+        );
+        assert_eq!(
+            polygon.interior_points(),
+            Some((covered - boundary) as u128),
+            "{vertices:?}"
+        );
+    }
+}
+
+#[test]
+fn metrics_of_the_example() {
+    let example = polygon(&[
+        [7, 1],
+        [11, 1],
+        [11, 7],
+        [9, 7],
+        [9, 5],
+        [2, 5],
+        [2, 3],
+        [7, 3],
+    ]);
+    assert_eq!(example.area(), Some(30));
+    assert_eq!(example.boundary_points(), Some(30));
+    assert_eq!(example.interior_points(), Some(16));
+    // Far apart coordinates still fit
+    let big = u64::MAX;
+    let square = RectilinearPolygon::new(vec![
+        Point([0, 0]),
+        Point([big, 0]),
+        Point([big, big]),
+        Point([0, big]),
+    ])
+    .unwrap();
+    assert_eq!(square.area(), Some(u128::from(big) * u128::from(big)));
+    assert_eq!(square.boundary_points(), Some(4 * u128::from(big)));
+    let huge = i128::MAX;
+    let square = RectilinearPolygon::new(vec![
+        Point([-huge, -huge]),
+        Point([huge, -huge]),
+        Point([huge, huge]),
+        Point([-huge, huge]),
+    ])
+    .unwrap();
+    assert_eq!(square.area(), None);
+}
+
+#[test]
+fn invalid_polygons_say_why() {
+    let error = |vertices: &[[i32; 2]]| {
+        RectilinearPolygon::new(vertices.iter().map(|&v| Point(v)).collect())
+            .unwrap_err()
+            .to_string()
+    };
+    assert_eq!(error(&[]), "the polygon has no vertices");
+    assert_eq!(
+        error(&[[0, 0], [2, 0], [2, 0], [2, 2], [0, 2]]),
+        "vertex 2 at 2,0 is the same as the one before it"
+    );
+    assert_eq!(
+        error(&[[0, 0], [2, 0], [3, 2], [0, 2]]),
+        "the edge from vertex 1 at 2,0 to 3,2 is neither horizontal nor vertical"
+    );
+    assert_eq!(
+        error(&[[0, 0], [2, 0], [2, 2], [1, 2]]),
+        "the polygon doesn't close: the last vertex at 1,2 shares no row or column with the first at 0,0"
+    );
+    // A figure of eight, and a loop that turns back on itself
+    assert_eq!(
+        error(&[[0, 0], [2, 0], [2, 4], [4, 4], [4, 2], [0, 2]]),
+        "the edges from vertices 1 and 4 intersect"
+    );
+    assert_eq!(
+        error(&[[0, 0], [3, 0], [1, 0], [1, 2], [0, 2]]),
+        "the edges from vertices 0 and 1 intersect"
+    );
+    assert!(RectilinearPolygon::new(vec![Point([0, 0]), Point([1, 0])]).is_err());
+    // Edges that continue in the same direction are fine
+    assert!(RectilinearPolygon::new(vec![
+        Point([0, 0]),
+        Point([1, 0]),
+        Point([2, 0]),
+        Point([2, 1]),
+        Point([0, 1])
+    ])
+    .is_ok());
+}
+
+#[test]
+fn largest_rectangle_matches_brute_force() {
+    let mut rng = generate::rng(23);
+    for round in 0..3000 {
+        // Small ranges have lots of shared rows, columns and repeated points
+        let range = [3, 10, 1000][round % 3];
+        let n = rng.random_range(0..30);
+        let points: Vec<Point2<i64>> = (0..n)
+            .map(|_| {
+                Point([
+                    rng.random_range(-range..range),
+                    rng.random_range(-range..range),
+                ])
+            })
+            .collect();
+        let area = |a: Point2<i64>, b: Point2<i64>| {
+            u128::from(a.abs_diff(b).0.iter().map(|d| d + 1).product::<u64>())
+        };
+        let expected = points
+            .iter()
+            .tuple_combinations()
+            .map(|(&a, &b)| area(a, b))
+            .max();
+        let actual = largest_rectangle(&points);
+        assert_eq!(actual.map(|r| r.area), expected, "{points:?}");
+        if let Some(LargestRectangle {
+            corners: (a, b),
+            area: found,
+        }) = actual
+        {
+            assert!(points.contains(&a) && points.contains(&b));
+            assert_eq!(area(a, b), found);
+        }
+    }
+}
+
+#[test]
+fn largest_rectangle_of_the_example() {
+    let reds = [
+        [7, 1],
+        [11, 1],
+        [11, 7],
+        [9, 7],
+        [9, 5],
+        [2, 5],
+        [2, 3],
+        [7, 3],
+    ]
+    .map(Point);
+    let LargestRectangle { corners, area } = largest_rectangle(&reds).unwrap();
+    assert_eq!(area, 50);
+    // Either of the rectangles that span the whole loop
+    assert_eq!(corners.0.abs_diff(corners.1), Point([9_u32, 4]));
+    let far = [Point([0, 0]), Point([u64::MAX, u64::MAX])];
+    assert_eq!(largest_rectangle(&far).unwrap().area, u128::MAX);
+    assert_eq!(largest_rectangle(&far[..1]), None);
+}
@@ -0,0 +1,409 @@
+// The grid conventions: x is the column, y is the row, and the first line of text is y = 0
+use aoclib::grid::{
+    clockwise, parse_direction, CardinalDirection, Connectivity, Direction, Grid, IPoint, PointSet,
+    Rect, SparseGrid, StepError, Symmetry, Transformable, UPoint,
+};
+
+fn digits(input: &str) -> Grid<u32> {
+    Grid::parse(input, |c| c.to_digit(10)).expect("valid grid")
+}
+
+#[test]
+fn parse_and_display_round_trip() {
+    let input = "123\n456\n";
+    let grid = digits(input);
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid.to_string(), input);
+    // CRLF and trailing blank lines are fine too
+    assert_eq!(digits("123\r\n456\r\n\r\n"), grid);
+}
+
+#[test]
+fn x_is_the_column_and_y_the_row() {
+    let grid = digits("123\n456");
+    assert_eq!(grid[UPoint { x: 2, y: 0 }], 3);
+    assert_eq!(grid[UPoint { x: 0, y: 1 }], 4);
+    assert_eq!(grid.get(UPoint { x: 3, y: 0 }), None);
+    assert_eq!(grid.row(1), &[4, 5, 6]);
+    assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+    assert_eq!(grid.columns().count(), 3);
+    let cells: Vec<_> = grid.cells().map(|(p, &c)| (p.x, p.y, c)).collect();
+    assert_eq!(cells[..4], [(0, 0, 1), (1, 0, 2), (2, 0, 3), (0, 1, 4)]);
+}
+
+#[test]
+fn neighbors_stay_in_bounds() {
+    let grid = Grid::new(3, 2, ());
+    let corner = UPoint { x: 0, y: 0 };
+    assert_eq!(grid.neighbors(corner).count(), 2);
+    assert_eq!(grid.diagonal_neighbors(corner).count(), 3);
+    let edge = UPoint { x: 1, y: 1 };
+    assert_eq!(grid.neighbors(edge).count(), 3);
+    assert_eq!(grid.diagonal_neighbors(edge).count(), 5);
+    assert!(grid.diagonal_neighbors(edge).all(|p| grid.contains(p)));
+}
+
+#[test]
+fn parse_errors_point_at_the_problem() {
+    let parse = |input| Grid::parse(input, |c| c.to_digit(10)).unwrap_err();
+    let e = parse("123\n4x6");
+    assert_eq!((e.line, e.column), (2, 2));
+    let e = parse("123\n45");
+    assert_eq!((e.line, e.column), (2, 3));
+    let e = parse("123\n4567");
+    assert_eq!((e.line, e.column), (2, 4));
+    assert!(Grid::parse("", |c| c.to_digit(10)).is_err());
+}
+
+#[test]
+fn rect_extents_are_exclusive() {
+    let rect = Rect::new(4, 3);
+    assert!(rect.contains(UPoint { x: 3, y: 2 }));
+    assert!(!rect.contains(UPoint { x: 4, y: 2 }));
+    assert!(!rect.contains(UPoint { x: 3, y: 3 }));
+    assert_eq!(rect.points().count(), rect.area());
+    let inner = Rect::from_corners(UPoint { x: 1, y: 1 }, UPoint { x: 3, y: 2 });
+    assert_eq!((inner.width(), inner.height()), (2, 1));
+    assert!(Rect::from_corners(UPoint { x: 2, y: 0 }, UPoint { x: 1, y: 5 }).is_empty());
+}
+
+#[test]
+fn neighbors_within_matches_brute_force() {
+    let rect = Rect::from_corners(UPoint { x: 1, y: 2 }, UPoint { x: 7, y: 6 });
+    for p in Rect::new(9, 9).points() {
+        for r in 0..4 {
+            let expected: Vec<UPoint> = rect
+                .points()
+                .filter(|&q| q != p && q.x.abs_diff(p.x) <= r && q.y.abs_diff(p.y) <= r)
+                .collect();
+            assert_eq!(rect.neighbors_within(p, r).collect::<Vec<_>>(), expected);
+        }
+        let mut around: Vec<_> = rect.diagonal_neighbors(p).map(|q| (q.y, q.x)).collect();
+        around.sort();
+        let within: Vec<_> = rect.neighbors_within(p, 1).map(|q| (q.y, q.x)).collect();
+        assert_eq!(around, within);
+        assert!(rect
+            .neighbors(p)
+            .all(|q| q.x.abs_diff(p.x) + q.y.abs_diff(p.y) == 1));
+    }
+}
+
+#[test]
+fn neighbors_within_any_radius() {
+    let rect = Rect::new(4, 3);
+    let p = UPoint { x: 1, y: 2 };
+    let all: Vec<_> = rect.points().filter(|&q| q != p).collect();
+    for r in [4, usize::MAX - 1, usize::MAX] {
+        assert_eq!(rect.neighbors_within(p, r).collect::<Vec<_>>(), all);
+    }
+    let corner = UPoint {
+        x: usize::MAX,
+        y: usize::MAX,
+    };
+    let huge = Rect::from_corners(
+        UPoint {
+            x: usize::MAX - 2,
+            y: usize::MAX - 1,
+        },
+        corner,
+    );
+    assert_eq!(huge.neighbors_within(corner, usize::MAX).count(), 2);
+}
+
+#[test]
+fn turns_are_consistent() {
+    for d in Direction::ALL {
+        assert_eq!(d.right().left(), d);
+        assert_eq!(d.right().right(), d.back());
+        assert_eq!(-d, d.back());
+        assert_eq!(d.rotate(8), d);
+        assert_eq!(d.rotate(-3), d.rotate(5));
+        assert_eq!(d.rotate(1).is_diagonal(), !d.is_diagonal());
+        // Turning right by 90° rotates the delta clockwise, which with y pointing down is
+        // (x, y) -> (-y, x)
+        let IPoint { x, y } = d.delta();
+        assert_eq!(d.right().delta(), IPoint { x: -y, y: x });
+        assert_eq!(Direction::from_delta(d.delta()), Some(d));
+    }
+    assert_eq!(Direction::from_delta(IPoint { x: 2, y: 0 }), None);
+    for c in [
+        CardinalDirection::North,
+        CardinalDirection::East,
+        CardinalDirection::South,
+        CardinalDirection::West,
+    ] {
+        assert_eq!(Direction::from(clockwise(c)), Direction::from(c).right());
+        assert_eq!(CardinalDirection::try_from(Direction::from(c)), Ok(c));
+    }
+    assert!(CardinalDirection::try_from(Direction::NorthEast).is_err());
+}
+
+#[test]
+fn directions_parse() {
+    for (chars, d) in [
+        ("^NU", Direction::North),
+        (">ER", Direction::East),
+        ("vSD", Direction::South),
+        ("<WL", Direction::West),
+    ] {
+        for c in chars.chars() {
+            assert_eq!(Direction::try_from(c), Ok(d));
+            assert_eq!(parse_direction(&c.to_string()).map(|(_, d)| d), Ok(d));
+        }
+    }
+    for d in Direction::ALL {
+        assert_eq!(d.to_string().parse(), Ok(d));
+    }
+    assert!("x".parse::<Direction>().is_err());
+    assert!("NEE".parse::<Direction>().is_err());
+}
+
+#[test]
+fn steps_report_why_they_fail() {
+    let bounds = Rect::new(5, 5);
+    let p = UPoint { x: 1, y: 3 };
+    assert_eq!(Direction::North.step(p), Some(UPoint { x: 1, y: 2 }));
+    assert_eq!(Direction::NorthWest.step(UPoint { x: 0, y: 3 }), None);
+    assert_eq!(
+        Direction::SouthEast.step_n(p, 1, &bounds),
+        Ok(UPoint { x: 2, y: 4 })
+    );
+    assert_eq!(
+        Direction::West.step_n(p, 2, &bounds),
+        Err(StepError::Underflow)
+    );
+    assert_eq!(
+        Direction::South.step_n(p, 2, &bounds),
+        Err(StepError::OutOfBounds)
+    );
+    assert_eq!(
+        Direction::East.step_n(p, usize::MAX, &bounds),
+        Err(StepError::OutOfBounds)
+    );
+    assert_eq!(
+        Direction::between(p, UPoint { x: 0, y: 4 }),
+        Some(Direction::SouthWest)
+    );
+    assert_eq!(Direction::between(p, UPoint { x: 3, y: 3 }), None);
+}
+
+fn chars(input: &str) -> Grid<char> {
+    Grid::parse(input, Some).expect("valid grid")
+}
+
+// The fence prices of gardens where each plot is a region of equal letters: area times perimeter,
+// and area times the number of sides
+fn prices(input: &str) -> (usize, usize) {
+    let garden = chars(input);
+    let regions = garden.partition(Connectivity::Four).regions;
+    let price = |measure: fn(&aoclib::grid::Region) -> usize| {
+        regions.iter().map(|r| r.area() * measure(r)).sum()
+    };
+    (price(|r| r.perimeter()), price(|r| r.sides()))
+}
+
+#[test]
+fn regions_measure_their_fences() {
+    assert_eq!(prices("AAAA\nBBCD\nBBCC\nEEEC"), (140, 80));
+    // Holes have sides too
+    assert_eq!(prices("OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO"), (772, 436));
+    assert_eq!(prices("EEEEE\nEXXXX\nEEEEE\nEXXXX\nEEEEE"), (692, 236));
+    assert_eq!(
+        prices("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA"),
+        (1184, 368)
+    );
+    let big = "RRRRIICCFF\nRRRRIICCCF\nVVRRRCCFFF\nVVRCCCJFFF\nVVVVCJJCFE\n\
+               VVIVCCJJEE\nVVIIICJJEE\nMIIIIIJJEE\nMIIISIJEEE\nMMMISSJEEE";
+    assert_eq!(prices(big), (1930, 1206));
+}
+
+#[test]
+fn components_depend_on_connectivity() {
+    let grid = chars("#..#\n.#..\n..##\n#...");
+    let four = grid.components(Connectivity::Four, |&c| c == '#');
+    let eight = grid.components(Connectivity::Eight, |&c| c == '#');
+    assert_eq!(four.regions.len(), 5);
+    assert_eq!(eight.regions.len(), 3);
+    // Labels follow the first cell of each region, row by row
+    assert_eq!(four.labels[UPoint { x: 3, y: 0 }], Some(1));
+    assert_eq!(eight.labels[UPoint { x: 3, y: 2 }], Some(0));
+    assert_eq!(eight.labels[UPoint { x: 1, y: 0 }], None);
+    let region = &eight.regions[0];
+    assert_eq!(region.area(), 4);
+    let bounds = region.bounding_box();
+    assert_eq!(
+        (bounds.min.x, bounds.min.y, bounds.max.x, bounds.max.y),
+        (0, 0, 4, 3)
+    );
+    assert_eq!(
+        grid.flood_fill(UPoint { x: 0, y: 0 }, Connectivity::Four, |&c| c == '#'),
+        four.regions[0]
+    );
+    assert!(grid
+        .flood_fill(UPoint { x: 1, y: 0 }, Connectivity::Four, |&c| c == '#')
+        .points
+        .is_empty());
+}
+
+#[test]
+fn perimeter_and_sides_match_brute_force() {
+    // Every 4x4 pattern of one region, measured by walking the unit edges of the boundary
+    for bits in 0u32..1 << 16 {
+        let grid = Grid::from_fn(4, 4, |p| bits >> (p.y * 4 + p.x) & 1 == 1);
+        let Some(start) = grid.points().find(|&p| grid[p]) else {
+            continue;
+        };
+        let region = grid.flood_fill(start, Connectivity::Four, |&b| b);
+        if region.area() != bits.count_ones() as usize {
+            continue;
+        }
+        let inside = |x: i64, y: i64| {
+            x >= 0
+                && y >= 0
+                && region.contains(UPoint {
+                    x: x as usize,
+                    y: y as usize,
+                })
+        };
+        // Horizontal edges above each cell and vertical edges left of each, for a 6x6 frame
+        let horizontal = |x: i64, y: i64| inside(x, y) != inside(x, y - 1);
+        let vertical = |x: i64, y: i64| inside(x, y) != inside(x - 1, y);
+        let mut perimeter = 0;
+        let mut sides = 0;
+        for y in -1..=5 {
+            for x in -1..=5 {
+                perimeter += horizontal(x, y) as usize + vertical(x, y) as usize;
+                // An edge starts a side unless the previous edge in line has the same inside
+                if horizontal(x, y) && !(horizontal(x - 1, y) && inside(x - 1, y) == inside(x, y)) {
+                    sides += 1;
+                }
+                if vertical(x, y) && !(vertical(x, y - 1) && inside(x, y - 1) == inside(x, y)) {
+                    sides += 1;
+                }
+            }
+        }
+        assert_eq!(region.perimeter(), perimeter, "{grid:?}");
+        assert_eq!(region.sides(), sides, "{grid:?}");
+    }
+}
+
+#[test]
+fn sparse_grids_track_their_bounds() {
+    let mut grid = SparseGrid::new();
+    assert!(grid.bounds().is_empty());
+    grid.insert(IPoint { x: -2, y: 3 }, 'a');
+    grid.insert(IPoint { x: 1, y: -1 }, 'b');
+    grid.insert(IPoint { x: 0, y: 0 }, 'c');
+    let bounds = grid.bounds();
+    assert_eq!(
+        (bounds.min, bounds.max),
+        (IPoint { x: -2, y: -1 }, IPoint { x: 2, y: 4 })
+    );
+    assert_eq!(grid.to_string(), "...b\n..c.\n....\n....\na...\n");
+    // Removing a cell on the edge shrinks them again
+    assert_eq!(grid.remove(IPoint { x: -2, y: 3 }), Some('a'));
+    assert_eq!(grid.remove(IPoint { x: -2, y: 3 }), None);
+    let bounds = grid.bounds();
+    assert_eq!(
+        (bounds.min, bounds.max),
+        (IPoint { x: 0, y: -1 }, IPoint { x: 2, y: 1 })
+    );
+    grid.remove(IPoint { x: 0, y: 0 });
+    grid.remove(IPoint { x: 1, y: -1 });
+    assert!(grid.is_empty() && grid.bounds().is_empty());
+}
+
+#[test]
+fn sparse_grids_render_any_viewport() {
+    let grid: SparseGrid<u32> = [(IPoint { x: 0, y: 0 }, 1), (IPoint { x: 5, y: 5 }, 2)]
+        .into_iter()
+        .collect();
+    let viewport = Rect::from_corners(IPoint { x: -1, y: -1 }, IPoint { x: 2, y: 1 });
+    let render = |cell: Option<&u32>| cell.map_or(' ', |&c| char::from_digit(c, 10).unwrap());
+    assert_eq!(grid.render(viewport, render), "   \n 1 \n");
+}
+
+#[test]
+fn sparse_and_dense_grids_convert() {
+    let dense = chars("#..\n.#.\n..#\n...");
+    let sparse = SparseGrid::from_grid(dense.clone(), |&c| c == '#');
+    assert_eq!(sparse.len(), 3);
+    assert_eq!(sparse[IPoint { x: 2, y: 2 }], '#');
+    let bounds = sparse.bounds();
+    assert_eq!((bounds.width(), bounds.height()), (3, 3));
+    // Back through a viewport that adds the empty last row again
+    let viewport = Rect::from_corners(IPoint { x: 0, y: 0 }, IPoint { x: 3, y: 4 });
+    assert_eq!(
+        sparse.to_grid(viewport, |c| c.copied().unwrap_or('.')),
+        dense
+    );
+    assert_eq!(SparseGrid::from(dense).len(), 12);
+}
+
+#[test]
+fn symmetries_form_a_group() {
+    for a in Symmetry::ALL {
+        assert_eq!(a.then(a.inverse()), Symmetry::Identity);
+        for b in Symmetry::ALL {
+            let p = IPoint { x: 3, y: -7 };
+            assert_eq!(a.then(b).apply(p), b.apply(a.apply(p)));
+        }
+    }
+    assert_eq!(
+        Symmetry::Rotate90.then(Symmetry::Rotate90),
+        Symmetry::Rotate180
+    );
+    assert_eq!(
+        Symmetry::Transpose.then(Symmetry::FlipLeftRight),
+        Symmetry::Rotate90
+    );
+}
+
+#[test]
+fn grids_and_text_transform_alike() {
+    let grid = digits("123\n456");
+    let turn = |s| grid.transform(s).to_string();
+    assert_eq!(turn(Symmetry::Rotate90), "41\n52\n63\n");
+    assert_eq!(turn(Symmetry::Rotate270), "36\n25\n14\n");
+    assert_eq!(turn(Symmetry::FlipLeftRight), "321\n654\n");
+    assert_eq!(turn(Symmetry::FlipUpDown), "456\n123\n");
+    assert_eq!(turn(Symmetry::Transpose), "14\n25\n36\n");
+    assert_eq!(turn(Symmetry::AntiTranspose), "63\n52\n41\n");
+    for s in Symmetry::ALL {
+        assert_eq!("123\n456".to_string().transform(s), turn(s));
+        assert_eq!(grid.transform(s).transform(s.inverse()), grid);
+    }
+    // Ragged lines are padded with spaces
+    assert_eq!(
+        "ab\nc".to_string().transform(Symmetry::Transpose),
+        "ac\nb \n"
+    );
+    let square = [[1, 2], [3, 4]];
+    assert_eq!(square.transform(Symmetry::Rotate90), [[3, 1], [4, 2]]);
+}
+
+#[test]
+fn orbits_count_orientations() {
+    let shape =
+        |cells: &[(isize, isize)]| PointSet::new(cells.iter().map(|&(x, y)| IPoint { x, y }));
+    let square = shape(&[(0, 0), (1, 0), (0, 1), (1, 1)]);
+    let line = shape(&[(0, 0), (1, 0), (2, 0), (3, 0)]);
+    let s = shape(&[(1, 0), (2, 0), (0, 1), (1, 1)]);
+    let l = shape(&[(0, 0), (0, 1), (0, 2), (1, 2)]);
+    let sizes: Vec<_> = [&square, &line, &s, &l].map(|p| p.orbit().len()).into();
+    assert_eq!(sizes, [1, 2, 4, 8]);
+    // Every orientation has the same canonical form, and it is one of them
+    for image in l.orbit() {
+        assert_eq!(image.canonical(), l.canonical());
+    }
+    assert!(l.orbit().contains(&l.canonical()));
+    // Point sets don't care where they are
+    let moved = PointSet::new(l.points().iter().map(|&p| p + IPoint { x: -5, y: 9 }));
+    assert_eq!(moved, l);
+    let grid = Grid::from_fn(3, 2, |p| p.x + p.y == 1);
+    assert_eq!(grid.orbit().len(), 8);
+    assert_eq!(
+        grid.canonical(),
+        grid.transform(Symmetry::Rotate180).canonical()
+    );
+}
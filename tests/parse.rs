@@ -0,0 +1,147 @@
+// How the days' parsers treat leftover input and Windows line endings, and what parse errors look
+// like
+use std::fs;
+use std::path::Path;
+
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, line_ending, u64};
+use nom::multi::separated_list1;
+use nom::Parser;
+
+use aoclib::days;
+use aoclib::diagnostics;
+use aoclib::parse::{self, Strictness};
+use aoclib::solution::Part;
+
+fn example(day: u8) -> String {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("examples/{day:02}/1"));
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+}
+
+// Both answers for input, or the error from parsing it
+fn answers(day: u8, input: &str, strictness: Strictness) -> Result<Vec<String>, String> {
+    let day = days::get(day).expect("day is not registered");
+    let parsed = (day.parse)(input, strictness).map_err(|e| e.to_string())?;
+    Ok(Part::ALL
+        .iter()
+        .map(|&part| parsed.solve(part).expect("the example solves"))
+        .collect())
+}
+
+#[test]
+fn leftover_input_is_only_accepted_when_lenient() {
+    diagnostics::capture(true);
+    for day in [1, 2, 3, 5, 8, 9, 10, 11] {
+        let input = example(day);
+        let expected = answers(day, &input, Strictness::Strict).unwrap();
+        let garbage = format!("{}\n!garbage\n", input.trim_end());
+        let error = answers(day, &garbage, Strictness::Strict).unwrap_err();
+        assert!(
+            error.contains("parsing stopped here, expected the end of the input"),
+            "day {day:02}: {error}"
+        );
+        assert_eq!(
+            answers(day, &garbage, Strictness::Lenient),
+            Ok(expected),
+            "day {day:02}"
+        );
+    }
+    diagnostics::take();
+}
+
+#[test]
+fn crlf_inputs_parse_like_lf() {
+    diagnostics::capture(true);
+    for day in 1..=11 {
+        let input = example(day);
+        let crlf = input.replace('\n', "\r\n");
+        assert_eq!(
+            answers(day, &crlf, Strictness::Strict),
+            answers(day, &input, Strictness::Strict),
+            "day {day:02}"
+        );
+    }
+    diagnostics::take();
+    // Day 12 would need a MILP solver to solve, but should still parse
+    let day12 = "0:\r\n###\r\n#..\r\n###\r\n\r\n1:\r\n###\r\n.#.\r\n###\r\n\r\n4x4: 1 0\r\n";
+    let day = days::get(12).expect("day is not registered");
+    assert!((day.parse)(day12, Strictness::Strict).is_ok());
+}
+
+fn report<'a, O>(
+    parser: impl Parser<&'a str, Output = O, Error = parse::Error<&'a str>>,
+    input: &'a str,
+) -> String {
+    match parse::run(parser, input) {
+        Ok(_) => panic!("{input:?} parsed"),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[test]
+fn reports_point_at_the_failure() {
+    let rotation = || (alt((char('L'), char('R'))), u64);
+    let rotations = || separated_list1(line_ending, rotation());
+    assert_eq!(
+        report(rotations(), "X5\nR30\n"),
+        "Invalid input at line 1, column 1: expected 'L' or 'R'\n  |\n1 | X5\n  | ^"
+    );
+    // A bad line after the first just ends the list, and then the rest is left over
+    assert_eq!(
+        report(rotations(), "L68\nR30\nX5\n"),
+        "Invalid input at line 3, column 1: parsing stopped here, expected the end of the input\n  \
+         |\n3 | X5\n  | ^"
+    );
+    // Carriage returns aren't part of the line
+    assert_eq!(
+        report(rotations(), "L68\r\nR3x\r\n"),
+        "Invalid input at line 2, column 3: parsing stopped here, expected the end of the input\n  \
+         |\n2 | R3x\n  |   ^"
+    );
+    // The line number gets as wide a margin as it needs
+    let first = "L1\n".repeat(11);
+    assert_eq!(
+        report((tag(first.as_str()), rotation()), &(first.clone() + "R")),
+        "Invalid input at line 12, column 2: expected a number\n   \
+         |\n12 | R\n   |  ^"
+    );
+}
+
+#[test]
+fn reports_count_characters_not_bytes() {
+    assert_eq!(
+        report((tag("héllo wörld"), char(',')), "héllo wörld;"),
+        "Invalid input at line 1, column 12: expected ','\n  \
+         |\n1 | héllo wörld;\n  |            ^"
+    );
+    assert_eq!(
+        report(tag("ab"), "ü"),
+        "Invalid input at line 1, column 1: expected a keyword\n  |\n1 | ü\n  | ^"
+    );
+}
+
+#[test]
+fn reports_merge_alternatives_that_fail_at_the_same_place() {
+    // Both fail at the x, so either would have done
+    assert_eq!(
+        report(alt((char('a'), char('b'), u64.map(|_| 'n'))), "x"),
+        "Invalid input at line 1, column 1: expected 'a' or 'b' or a number\n  |\n1 | x\n  | ^"
+    );
+    // The first alternative got further, so only it is reported
+    assert_eq!(
+        report(alt(((char('a'), char('b')), (char('c'), char('d')))), "ax"),
+        "Invalid input at line 1, column 2: expected 'b'\n  |\n1 | ax\n  |  ^"
+    );
+    // The same expectation from two alternatives is only mentioned once
+    assert_eq!(
+        report(alt(((char('a'), char('b')), (char('a'), char('b')))), "a"),
+        "Invalid input at line 1, column 2: expected 'b'\n  |\n1 | a\n  |  ^"
+    );
+    // A context replaces what the parsers inside it expected
+    assert_eq!(
+        report(parse::coordinate, "3000000000"),
+        "Invalid input at line 1, column 1: expected a coordinate below 2^31\n  \
+         |\n1 | 3000000000\n  | ^"
+    );
+}
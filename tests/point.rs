@@ -0,0 +1,67 @@
+// Points of any dimension, checked against the same computations done in i128
+use proptest::prelude::*;
+
+use aoclib::grid::{IPoint, UPoint};
+use aoclib::parse;
+use aoclib::point::{self, Point, Point2, Point3};
+
+proptest! {
+    #[test]
+    fn distances_match_wide_arithmetic(a in any::<[i32; 3]>(), b in any::<[i32; 3]>()) {
+        let diffs: Vec<i128> = (0..3).map(|i| (i128::from(a[i]) - i128::from(b[i])).abs()).collect();
+        // The distances between i32 points are u32, so None when they don't fit in that
+        let fits = |d: i128| Some(d).filter(|&d| d <= i128::from(u32::MAX));
+        let (p, q) = (Point(a), Point(b));
+        prop_assert_eq!(p.manhattan(q).map(i128::from), fits(diffs.iter().sum()));
+        prop_assert_eq!(i128::from(p.chebyshev(q)), *diffs.iter().max().unwrap());
+        prop_assert_eq!(p.squared_euclidean(q).map(i128::from), fits(diffs.iter().map(|d| d * d).sum()));
+    }
+
+    #[test]
+    fn casts_check_every_coordinate(a in any::<[i64; 2]>()) {
+        let cast = Point(a).try_cast::<u32>();
+        let fits = a.iter().all(|&c| u32::try_from(c).is_ok());
+        prop_assert_eq!(cast.is_some(), fits);
+        if let Some(cast) = cast {
+            prop_assert_eq!(cast.try_cast::<i64>(), Some(Point(a)));
+        }
+    }
+}
+
+#[test]
+fn arithmetic_is_per_coordinate() {
+    let p = Point([1, -2, 3]);
+    let q = Point([4, 5, -6]);
+    assert_eq!(p + q, Point([5, 3, -3]));
+    assert_eq!(p - q, Point([-3, -7, 9]));
+    assert_eq!(-p, Point([-1, 2, -3]));
+    assert_eq!(p * 3, Point([3, -6, 9]));
+    let mut r = p;
+    r += q;
+    r -= p;
+    assert_eq!(r, q);
+    assert_eq!((p.x(), p.y(), p.z()), (1, -2, 3));
+    assert_eq!(q.abs_diff(p), Point([3u32, 7, 9]));
+}
+
+#[test]
+fn points_convert_to_grid_points() {
+    let p: Point2<usize> = UPoint { x: 3, y: 4 }.into();
+    assert_eq!(p, Point([3, 4]));
+    assert_eq!(UPoint::from(p), UPoint { x: 3, y: 4 });
+    let q: Point2<isize> = IPoint { x: -1, y: 2 }.into();
+    assert_eq!(q.try_cast::<usize>(), None);
+    assert_eq!(IPoint::from(q), IPoint { x: -1, y: 2 });
+}
+
+#[test]
+fn points_parse_and_display() {
+    let p: Point3<u64> = parse::run(point::xyz(parse::coordinate), "162,817,812").unwrap();
+    assert_eq!(p, Point([162, 817, 812]));
+    assert_eq!(p.to_string(), "162,817,812");
+    let q = parse::run(point::xy(nom::character::complete::i32), "-7,11").unwrap();
+    assert_eq!(q, Point([-7, 11]));
+    let e = parse::run(point::xyz(parse::coordinate), "1,2").unwrap_err();
+    assert_eq!((e.line, e.column), (1, 4));
+    assert!(parse::run(point::xy(parse::coordinate), "1,2,3").is_err());
+}
@@ -0,0 +1,85 @@
+// The stable polyfills should behave like the std features they stand in for
+#![allow(unstable_name_collisions)]
+
+use proptest::prelude::*;
+
+use aoclib::polyfill::{DivFloor, IteratorExt, MinMax, StringExt};
+
+proptest! {
+    #[test]
+    fn map_windows_matches_slice_windows(v in prop::collection::vec(any::<i32>(), 0..20)) {
+        let expected: Vec<i64> = v.windows(3).map(|w| w.iter().map(|&x| i64::from(x)).sum()).collect();
+        let actual: Vec<i64> = v.iter().map_windows(|w: &[&i32; 3]| w.iter().map(|&&x| i64::from(x)).sum()).collect();
+        prop_assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn div_floor_rounds_down(a in -10_000i64..10_000, b in (-100i64..100).prop_filter("nonzero", |b| *b != 0)) {
+        let q = a.div_floor(b);
+        // q is the largest integer with q <= a / b
+        if b > 0 {
+            prop_assert!(q * b <= a && a < (q + 1) * b);
+        } else {
+            prop_assert!(q * b >= a && a > (q + 1) * b);
+        }
+    }
+
+    #[test]
+    fn minmax_sorts(a in any::<i32>(), b in any::<i32>()) {
+        prop_assert_eq!(a.minmax(b), [a.min(b), a.max(b)]);
+    }
+
+    #[test]
+    fn into_chars_matches_chars(s in ".{0,20}", back in any::<bool>()) {
+        let (expected, actual): (Vec<char>, Vec<char>) = if back {
+            (s.chars().rev().collect(), s.clone().into_chars().rev().collect())
+        } else {
+            (s.chars().collect(), s.clone().into_chars().collect())
+        };
+        prop_assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn advance_by_reports_missing_items(len in 0usize..10, n in 0usize..20) {
+        let mut iter = 0..len;
+        let result = iter.advance_by(n).map_err(|k| k.get());
+        if n <= len {
+            prop_assert_eq!(result, Ok(()));
+            prop_assert_eq!(iter.next(), (n < len).then_some(n));
+        } else {
+            prop_assert_eq!(result, Err(n - len));
+        }
+    }
+}
+
+// Equal by the key, but distinguishable by the label
+#[derive(Debug)]
+struct Labelled(u8, char);
+
+impl PartialEq for Labelled {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Labelled {}
+
+impl PartialOrd for Labelled {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Labelled {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+#[test]
+fn minmax_keeps_equal_items_in_order() {
+    let [a, b] = Labelled(1, 'a').minmax(Labelled(1, 'b'));
+    assert_eq!((a.1, b.1), ('a', 'b'));
+    let [a, b] = Labelled(2, 'a').minmax(Labelled(1, 'b'));
+    assert_eq!((a.1, b.1), ('b', 'a'));
+}
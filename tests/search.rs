@@ -0,0 +1,121 @@
+// The searches against each other and against Bellman-Ford on random weighted grids
+use std::collections::BTreeMap;
+
+use proptest::prelude::*;
+
+use aoclib::grid::{Grid, Rect, UPoint};
+use aoclib::search::{astar, bfs, bfs_with, dijkstra, dijkstra_with, Indexed, Path, Unvisited};
+
+// Moving onto a cell costs its weight, and cells of weight 0 are walls
+fn weighted_grid() -> impl Strategy<Value = Grid<u32>> {
+    (1usize..8, 1usize..8)
+        .prop_flat_map(|(w, h)| (Just(w), Just(h), prop::collection::vec(0u32..10, w * h)))
+        .prop_map(|(w, h, weights)| Grid::from_fn(w, h, |p| weights[p.y * w + p.x]))
+}
+
+fn successors(grid: &Grid<u32>) -> impl FnMut(&UPoint) -> Vec<(UPoint, u32)> + '_ {
+    |&p| {
+        grid.neighbors(p)
+            .filter(|&q| grid[q] > 0)
+            .map(|q| (q, grid[q]))
+            .collect()
+    }
+}
+
+// The cheapest cost from start to every cell, by relaxing every edge until nothing changes
+fn bellman_ford(grid: &Grid<u32>, start: UPoint) -> Grid<Option<u32>> {
+    let mut costs = Grid::new(grid.width(), grid.height(), None);
+    costs[start] = Some(0);
+    loop {
+        let mut changed = false;
+        for p in grid.points() {
+            let Some(c) = costs[p] else { continue };
+            for (q, w) in successors(grid)(&p) {
+                if costs[q].is_none_or(|d| c + w < d) {
+                    costs[q] = Some(c + w);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            return costs;
+        }
+    }
+}
+
+fn check_path(grid: &Grid<u32>, path: &Path<UPoint, u32>) {
+    let cost: u32 = path.states[1..].iter().map(|&q| grid[q]).sum();
+    assert_eq!(cost, path.cost);
+    assert!(path
+        .states
+        .windows(2)
+        .all(|w| successors(grid)(&w[0]).iter().any(|&(q, _)| q == w[1])));
+}
+
+proptest! {
+    #[test]
+    fn weighted_searches_find_the_cheapest_path(grid in weighted_grid()) {
+        let start = UPoint { x: 0, y: 0 };
+        let goal = UPoint { x: grid.width() - 1, y: grid.height() - 1 };
+        let expected = bellman_ford(&grid, start)[goal];
+
+        let by_dijkstra = dijkstra([start], successors(&grid), |&p| p == goal);
+        // Every step costs at least 1, so the Manhattan distance never overestimates
+        let manhattan = |p: &UPoint| (p.x.abs_diff(goal.x) + p.y.abs_diff(goal.y)) as u32;
+        let by_astar = astar([start], successors(&grid), manhattan, |&p| p == goal);
+        let width = grid.width();
+        let indexed = Indexed::new(|p: &UPoint| p.y * width + p.x);
+        let by_indexed = dijkstra_with(indexed, [start], successors(&grid), |&p| p == goal);
+
+        for path in [&by_dijkstra, &by_astar, &by_indexed] {
+            prop_assert_eq!(path.as_ref().map(|p| p.cost), expected);
+            if let Some(path) = path {
+                check_path(&grid, path);
+                prop_assert_eq!(path.states[0], start);
+                prop_assert_eq!(*path.goal(), goal);
+            }
+        }
+    }
+
+    #[test]
+    fn bfs_counts_steps(grid in weighted_grid()) {
+        let start = UPoint { x: 0, y: 0 };
+        // With every weight 1, the cheapest path is the shortest
+        let unit = grid.map(|&w| u32::from(w > 0));
+        let expected = bellman_ford(&unit, start);
+        for goal in grid.points() {
+            let path = bfs([start], |&p| successors(&unit)(&p).into_iter().map(|(q, _)| q), |&p| p == goal);
+            prop_assert_eq!(path.as_ref().map(|p| p.cost as u32), expected[goal]);
+            if let Some(path) = path {
+                prop_assert_eq!(path.states.len(), path.cost + 1);
+            }
+        }
+    }
+}
+
+#[test]
+fn searches_stop_at_the_nearest_goal() {
+    let grid = Grid::new(10, 1, 1u32);
+    let starts = [UPoint { x: 4, y: 0 }, UPoint { x: 9, y: 0 }];
+    let goals = |p: &UPoint| p.x == 0 || p.x == 7;
+    let path = dijkstra(starts, successors(&grid), goals).unwrap();
+    assert_eq!((path.cost, path.goal().x), (2, 7));
+    let path = bfs(starts, |&p| Rect::new(10, 1).neighbors(p), goals).unwrap();
+    assert_eq!((path.cost, path.states[0].x), (2, 9));
+}
+
+#[test]
+fn visited_strategies_agree() {
+    // A binary tree of numbers, where n has children 2n and 2n + 1
+    let children = |&n: &u64| [2 * n, 2 * n + 1];
+    let goal = |&n: &u64| n == 45;
+    let tree = bfs_with(Unvisited, [1], children, goal).unwrap();
+    let map = bfs_with(BTreeMap::new(), [1], children, goal).unwrap();
+    assert_eq!(tree, map);
+    assert_eq!(tree.states, vec![1, 2, 5, 11, 22, 45]);
+    // The goal can't be reached
+    assert_eq!(
+        bfs([1u64], |&n| (n < 100).then_some(n + 2), |&n| n == 50),
+        None
+    );
+}